@@ -30,3 +30,47 @@ fn bench_this_crate(b: &mut Bencher) {
         })
     });
 }
+
+const MIXED_INPUT: &str = "Prince supports Unicode text in many scripts: Ελληνικά (Greek), Русский (Russian), Türkçe (Turkish) with its dotted İ and dotless ı, Deutsch with Straße and GROẞ, ქართული (Georgian), 日本語 (Japanese), and 한국어 (Korean). Mostly ASCII documents with occasional accented letters such as café, naïve, and Ångström are the common case.";
+
+const NON_ASCII_INPUT: &str = "Η γρήγορη καφέ αλεπού πηδάει πάνω από τον τεμπέλη σκύλο. Съешь же ещё этих мягких французских булок, да выпей чаю. Pijamalı hasta yağız şoföre çabucak güvendi. სწრაფი ყავისფერი მელა ხტება ზარმაც ძაღლზე.";
+
+#[bench]
+fn bench_lowercase_str_std(b: &mut Bencher) {
+    b.iter(|| test::black_box(INPUT).to_lowercase());
+}
+
+#[bench]
+fn bench_lowercase_str_this_crate(b: &mut Bencher) {
+    b.iter(|| unicode_case_mapping::lowercase_str(test::black_box(INPUT)));
+}
+
+#[bench]
+fn bench_uppercase_str_std(b: &mut Bencher) {
+    b.iter(|| test::black_box(INPUT).to_uppercase());
+}
+
+#[bench]
+fn bench_uppercase_str_this_crate(b: &mut Bencher) {
+    b.iter(|| unicode_case_mapping::uppercase_str(test::black_box(INPUT)));
+}
+
+#[bench]
+fn bench_mixed_lowercase_str_std(b: &mut Bencher) {
+    b.iter(|| test::black_box(MIXED_INPUT).to_lowercase());
+}
+
+#[bench]
+fn bench_mixed_lowercase_str_this_crate(b: &mut Bencher) {
+    b.iter(|| unicode_case_mapping::lowercase_str(test::black_box(MIXED_INPUT)));
+}
+
+#[bench]
+fn bench_non_ascii_uppercase_str_std(b: &mut Bencher) {
+    b.iter(|| test::black_box(NON_ASCII_INPUT).to_uppercase());
+}
+
+#[bench]
+fn bench_non_ascii_uppercase_str_this_crate(b: &mut Bencher) {
+    b.iter(|| unicode_case_mapping::uppercase_str(test::black_box(NON_ASCII_INPUT)));
+}
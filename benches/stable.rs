@@ -0,0 +1,185 @@
+//! Benchmarks that run on stable Rust, without the `test` crate.
+//!
+//! Run with `cargo bench --bench stable`. Arguments that don't start with `-` filter the
+//! benchmarks by name, for example `cargo bench --bench stable -- greek`.
+
+use std::env;
+use std::mem;
+use std::ops::Range;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+use unicode_case_mapping::{
+    case_folded, case_folded_u32, lowercase_str, to_lowercase, to_lowercase_u32, to_titlecase,
+    to_titlecase_u32, to_uppercase, to_uppercase_u32, uppercase_str,
+};
+
+const CORPORA: &[(&str, &str)] = &[
+    ("english", include_str!("corpus/english.txt")),
+    ("greek", include_str!("corpus/greek.txt")),
+    ("cyrillic", include_str!("corpus/cyrillic.txt")),
+    ("turkish", include_str!("corpus/turkish.txt")),
+    ("georgian", include_str!("corpus/georgian.txt")),
+    ("cjk", include_str!("corpus/cjk.txt")),
+    ("emoji", include_str!("corpus/emoji.txt")),
+];
+
+/// Time spent running each benchmark before measuring.
+const WARM_UP: Duration = Duration::from_millis(200);
+
+/// Number of timed samples taken of each benchmark.
+const SAMPLES: usize = 20;
+
+/// Target duration of each sample.
+const SAMPLE_TIME: Duration = Duration::from_millis(50);
+
+/// Prevent the optimiser from treating `value` as a constant or discarding it.
+fn black_box<T>(value: T) -> T {
+    // SAFETY: `value` is read once and then forgotten, so it is not dropped twice.
+    unsafe {
+        let copy = ptr::read_volatile(&value);
+        mem::forget(value);
+        copy
+    }
+}
+
+struct Bencher {
+    filters: Vec<String>,
+}
+
+impl Bencher {
+    /// Time `f`, which processes `bytes` bytes of input on each call.
+    fn bench<R, F: FnMut() -> R>(&self, name: &str, bytes: usize, mut f: F) {
+        if !self.filters.is_empty() && !self.filters.iter().any(|filter| name.contains(filter)) {
+            return;
+        }
+
+        // Warm up, estimating the number of iterations that fit in a sample
+        let start = Instant::now();
+        let mut iterations = 0u64;
+        while start.elapsed() < WARM_UP {
+            black_box(f());
+            iterations += 1;
+        }
+        let per_sample = (iterations * nanos(SAMPLE_TIME) / nanos(start.elapsed())).max(1);
+
+        let mut samples: Vec<f64> = (0..SAMPLES)
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..per_sample {
+                    black_box(f());
+                }
+                nanos(start.elapsed()) as f64 / per_sample as f64
+            })
+            .collect();
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median = samples[SAMPLES / 2];
+        let spread = samples[SAMPLES * 3 / 4] - samples[SAMPLES / 4];
+        println!(
+            "{:<40} {:>12.1} ns/iter (+/- {:>8.1}) {:>10.1} MB/s",
+            name,
+            median,
+            spread,
+            bytes as f64 * 1e3 / median
+        );
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_secs() * 1_000_000_000 + u64::from(duration.subsec_nanos())
+}
+
+fn bench_chars(bencher: &Bencher, corpus: &str, text: &str) {
+    let bytes = text.len();
+    bencher.bench(&format!("{}/to_lowercase", corpus), bytes, || {
+        black_box(text).chars().for_each(|chr| {
+            black_box(to_lowercase(chr));
+        })
+    });
+    bencher.bench(&format!("{}/to_lowercase/std", corpus), bytes, || {
+        black_box(text).chars().for_each(|chr| {
+            chr.to_lowercase().for_each(|c| {
+                black_box(c);
+            })
+        })
+    });
+    bencher.bench(&format!("{}/to_uppercase", corpus), bytes, || {
+        black_box(text).chars().for_each(|chr| {
+            black_box(to_uppercase(chr));
+        })
+    });
+    bencher.bench(&format!("{}/to_uppercase/std", corpus), bytes, || {
+        black_box(text).chars().for_each(|chr| {
+            chr.to_uppercase().for_each(|c| {
+                black_box(c);
+            })
+        })
+    });
+    bencher.bench(&format!("{}/to_titlecase", corpus), bytes, || {
+        black_box(text).chars().for_each(|chr| {
+            black_box(to_titlecase(chr));
+        })
+    });
+    bencher.bench(&format!("{}/case_folded", corpus), bytes, || {
+        black_box(text).chars().for_each(|chr| {
+            black_box(case_folded(chr));
+        })
+    });
+}
+
+fn bench_strings(bencher: &Bencher, corpus: &str, text: &str) {
+    let bytes = text.len();
+    bencher.bench(&format!("{}/lowercase_str", corpus), bytes, || {
+        lowercase_str(black_box(text))
+    });
+    bencher.bench(&format!("{}/lowercase_str/std", corpus), bytes, || {
+        black_box(text).to_lowercase()
+    });
+    bencher.bench(&format!("{}/uppercase_str", corpus), bytes, || {
+        uppercase_str(black_box(text))
+    });
+    bencher.bench(&format!("{}/uppercase_str/std", corpus), bytes, || {
+        black_box(text).to_uppercase()
+    });
+}
+
+type Lookup = fn(u32) -> u32;
+
+/// Look up every code point in `range`, which exercises all levels of the tables independent of
+/// any particular script.
+fn bench_lookups(bencher: &Bencher, name: &str, range: Range<u32>) {
+    let count = (range.end - range.start) as usize;
+    let lookups: &[(&str, Lookup)] = &[
+        ("to_lowercase_u32", |cp| to_lowercase_u32(cp)[0]),
+        ("to_uppercase_u32", |cp| to_uppercase_u32(cp)[0]),
+        ("to_titlecase_u32", |cp| to_titlecase_u32(cp)[0]),
+        ("case_folded_u32", |cp| {
+            case_folded_u32(cp).map_or(0, |folded| folded.get())
+        }),
+    ];
+    for &(function, lookup) in lookups {
+        // Report throughput as if each code point were a four byte character
+        bencher.bench(&format!("lookup/{}/{}", name, function), count * 4, || {
+            black_box(range.clone()).fold(0, |acc, cp| acc ^ lookup(cp))
+        });
+    }
+}
+
+fn main() {
+    let bencher = Bencher {
+        filters: env::args()
+            .skip(1)
+            .filter(|arg| !arg.starts_with('-'))
+            .collect(),
+    };
+
+    for &(corpus, text) in CORPORA {
+        bench_chars(&bencher, corpus, text);
+    }
+    for &(corpus, text) in CORPORA {
+        bench_strings(&bencher, corpus, text);
+    }
+    bench_lookups(&bencher, "latin", 0..0x250);
+    bench_lookups(&bencher, "bmp", 0..0x10000);
+    bench_lookups(&bencher, "all", 0..0x110000);
+}
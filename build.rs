@@ -1,4 +1,7 @@
+#[path = "src/case_folding_simple.rs"]
+mod case_folding_simple;
 #[path = "src/tables.rs"]
+#[allow(clippy::redundant_static_lifetimes)]
 mod tables;
 
 use std::cmp::Ordering;
@@ -16,9 +19,17 @@ const SHIFT: u32 = block::LAST_INDEX.count_ones();
 type Row = ([u32; 2], [u32; 3], [u32; 3]);
 
 fn main() {
-    let output_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("case_mapping.rs");
-
-    write_table(&output_path, &compile_table());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let case_folding_inverse = compile_case_folding_inverse();
+
+    write_table(
+        &out_dir.join("case_mapping.rs"),
+        &compile_table(&case_folding_inverse),
+    );
+    write_case_folding_inverse(
+        &out_dir.join("case_folding_inverse.rs"),
+        &case_folding_inverse,
+    );
 }
 
 struct CompiledTable {
@@ -26,9 +37,10 @@ struct CompiledTable {
     address_to_block_index: Vec<(u32, usize)>,
     last_code_point: u32,
     records: Vec<Row>,
+    simple_mappings: Vec<(u32, [u32; 3])>,
 }
 
-fn compile_table() -> CompiledTable {
+fn compile_table(case_folding_inverse: &BTreeMap<u32, Vec<u32>>) -> CompiledTable {
     let (mappings, codepoint_to_mapping_index) = compile_mappings();
 
     let mut blocks = Vec::new();
@@ -71,6 +83,7 @@ fn compile_table() -> CompiledTable {
         address_to_block_index,
         last_code_point,
         records: mappings,
+        simple_mappings: compile_simple_mappings(case_folding_inverse),
     }
 }
 
@@ -119,33 +132,97 @@ fn compile_mappings() -> (Vec<Row>, BTreeMap<u32, u16>) {
     (mappings, offsets)
 }
 
+/// Derives the simple (single code point) lower, upper, and titlecase mappings of the code points
+/// that have a full mapping of more than one code point. The simple mappings of all other code
+/// points are the same as their full mappings.
+fn compile_simple_mappings(case_folding_inverse: &BTreeMap<u32, Vec<u32>>) -> Vec<(u32, [u32; 3])> {
+    let mut codepoints = BTreeSet::new();
+    for table in &[tables::LOWER, tables::UPPER, tables::TITLE] {
+        codepoints.extend(
+            table
+                .iter()
+                .filter(|(_, mapping)| mapping.len() > 1)
+                .map(|(cp, _)| *cp),
+        );
+    }
+
+    codepoints
+        .into_iter()
+        .map(|cp| {
+            // The only expanding lowercase mapping is İ → i̇, whose simple mapping is the first
+            // code point.
+            let lower = lookup(cp, tables::LOWER).map_or(cp, |mapping| mapping[0]);
+            let upper = simple_mapping(cp, tables::UPPER, case_folding_inverse);
+            let title = simple_mapping(cp, tables::TITLE, case_folding_inverse);
+            (cp, [lower, upper, title])
+        })
+        .collect()
+}
+
+/// The simple mapping of `codepoint` in `table`.
+///
+/// When the full mapping expands, the simple mapping is the titlecase character that is
+/// equivalent under case folding, such as ᾳ → ᾼ, or the code point itself if there is none, such
+/// as ß.
+fn simple_mapping(
+    codepoint: u32,
+    table: &'static [(u32, &'static [u32])],
+    case_folding_inverse: &BTreeMap<u32, Vec<u32>>,
+) -> u32 {
+    match lookup(codepoint, table) {
+        Some(&[single]) => single,
+        Some(_) => {
+            let folded =
+                case_folding_simple::case_folding_simple(codepoint).map_or(codepoint, |f| f.get());
+            case_folding_inverse
+                .get(&folded)
+                .and_then(|class| {
+                    class
+                        .iter()
+                        .cloned()
+                        .find(|&other| other != codepoint && is_titlecase(other))
+                })
+                .unwrap_or(codepoint)
+        }
+        None => codepoint,
+    }
+}
+
+/// A titlecase character has both a lowercase and uppercase mapping
+fn is_titlecase(codepoint: u32) -> bool {
+    lookup(codepoint, tables::LOWER).is_some() && lookup(codepoint, tables::UPPER).is_some()
+}
+
 // If source is shorter than dest, it's assumed that the trailing values of dest are initialised
 // to a suitable value (I.e. 0).
 fn fill(source: &[u32], dest: &mut [u32]) {
     assert!(source.len() <= dest.len());
-    for i in 0..source.len() {
-        dest[i] = source[i];
-    }
+    dest[..source.len()].copy_from_slice(source);
 }
 
 fn write_table(path: &Path, compiled_table: &CompiledTable) {
     let mut output =
-        File::create(&path).expect(&format!("unable to open {}", path.to_string_lossy()));
+        File::create(path).unwrap_or_else(|_| panic!("unable to open {}", path.to_string_lossy()));
 
     writeln!(output, "pub type Row = ([u32; 2], [u32; 3], [u32; 3]);").unwrap();
 
     writeln!(
         output,
-        "\nconst LAST_CODEPOINT: u32 = 0x{:X};",
+        "\npub(crate) const LAST_CODEPOINT: u32 = 0x{:X};",
         compiled_table.last_code_point
     )
     .unwrap();
-    writeln!(output, "\nconst BLOCK_SIZE: usize = {};", block::SIZE).unwrap();
+    writeln!(
+        output,
+        "\npub(crate) const BLOCK_SIZE: usize = {};",
+        block::SIZE
+    )
+    .unwrap();
 
     // Write out the records
     writeln!(
         output,
-        "\nconst CASE_MAPPING_RECORDS: [Row; {}] = [",
+        "\npub(crate) static CASE_MAPPING_RECORDS: [Row; {}] = [",
         compiled_table.records.len()
     )
     .unwrap();
@@ -154,10 +231,22 @@ fn write_table(path: &Path, compiled_table: &CompiledTable) {
     }
     write!(output, "];\n\n").unwrap();
 
+    // Write out the simple mappings that differ from the records
+    writeln!(
+        output,
+        "\npub(crate) static SIMPLE_CASE_MAPPINGS: [(u32, [u32; 3]); {}] = [",
+        compiled_table.simple_mappings.len()
+    )
+    .unwrap();
+    for (codepoint, simple) in &compiled_table.simple_mappings {
+        writeln!(output, "    (0x{:04X}, {:?}),", codepoint, simple).unwrap();
+    }
+    write!(output, "];\n\n").unwrap();
+
     // Write out the blocks in address order
     writeln!(
         output,
-        "\nconst CASE_MAPPING_BLOCKS: [u16; {}] = [",
+        "\npub(crate) static CASE_MAPPING_BLOCKS: [u16; {}] = [",
         compiled_table.blocks.len() * block::SIZE
     )
     .unwrap();
@@ -192,7 +281,7 @@ fn write_table(path: &Path, compiled_table: &CompiledTable) {
     // Write out the array that maps case mapping to offsets
     writeln!(
         output,
-        "\nconst CASE_MAPPING_BLOCK_OFFSETS: [u16; {}] = [",
+        "\npub(crate) static CASE_MAPPING_BLOCK_OFFSETS: [u16; {}] = [",
         compiled_table.address_to_block_index.len()
     )
     .unwrap();
@@ -203,6 +292,46 @@ fn write_table(path: &Path, compiled_table: &CompiledTable) {
     writeln!(output, "];").unwrap();
 }
 
+/// Collects the code points that case fold to each code point, keyed by the folded code point.
+///
+/// Each list includes the folded code point itself and is sorted.
+fn compile_case_folding_inverse() -> BTreeMap<u32, Vec<u32>> {
+    let mut inverse: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for codepoint in 0..=0x10FFFF {
+        if let Some(folded) = case_folding_simple::case_folding_simple(codepoint) {
+            inverse.entry(folded.get()).or_default().push(codepoint);
+        }
+    }
+    for (&folded, codepoints) in inverse.iter_mut() {
+        codepoints.push(folded);
+        codepoints.sort();
+    }
+    inverse
+}
+
+fn write_case_folding_inverse(path: &Path, inverse: &BTreeMap<u32, Vec<u32>>) {
+    let mut output =
+        File::create(path).unwrap_or_else(|_| panic!("unable to open {}", path.to_string_lossy()));
+
+    writeln!(
+        output,
+        "static CASE_FOLDING_INVERSE: [(u32, &[char]); {}] = [",
+        inverse.len()
+    )
+    .unwrap();
+    for (folded, codepoints) in inverse {
+        write!(output, "    (0x{:04X}, &[", folded).unwrap();
+        for (i, codepoint) in codepoints.iter().enumerate() {
+            if i != 0 {
+                write!(output, ", ").unwrap();
+            }
+            write!(output, "'\\u{{{:04X}}}'", codepoint).unwrap();
+        }
+        writeln!(output, "]),").unwrap();
+    }
+    writeln!(output, "];").unwrap();
+}
+
 /// Lookup this code point in `table`
 fn lookup(codepoint: u32, table: &'static [(u32, &'static [u32])]) -> Option<&'static [u32]> {
     table
@@ -0,0 +1,49 @@
+//! Checks the byte-level conversions of possibly invalid UTF-8.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::str;
+use unicode_case_mapping::{
+    case_fold_bytes, lowercase_bytes, lowercase_str, uppercase_bytes, uppercase_str, InvalidUtf8,
+};
+
+fn convert(input: &[u8], mode: InvalidUtf8, f: fn(&[u8], InvalidUtf8, &mut Vec<u8>)) -> Vec<u8> {
+    let mut out = Vec::new();
+    f(input, mode, &mut out);
+    out
+}
+
+fuzz_target!(|data: &[u8]| {
+    let lower = convert(data, InvalidUtf8::Replace, lowercase_bytes);
+    let upper = convert(data, InvalidUtf8::Replace, uppercase_bytes);
+    let folded = convert(data, InvalidUtf8::Replace, case_fold_bytes);
+    for out in &[&lower, &upper, &folded] {
+        assert!(str::from_utf8(out).is_ok());
+    }
+
+    // Simple case folding is idempotent
+    assert_eq!(
+        convert(&folded, InvalidUtf8::Replace, case_fold_bytes),
+        folded
+    );
+
+    // Invalid sequences are replaced in the same manner as `from_utf8_lossy`
+    let lossy = String::from_utf8_lossy(data);
+    assert_eq!(lower, lowercase_str(&lossy).as_bytes());
+    assert_eq!(upper, uppercase_str(&lossy).as_bytes());
+
+    // Passing invalid sequences through leaves valid input unaffected
+    let passed = convert(data, InvalidUtf8::PassThrough, lowercase_bytes);
+    if str::from_utf8(data).is_ok() {
+        assert_eq!(passed, lower);
+    } else {
+        assert!(str::from_utf8(&passed).is_err());
+    }
+
+    // Appending to `out` leaves its existing contents alone
+    let mut out = data.to_vec();
+    lowercase_bytes(data, InvalidUtf8::PassThrough, &mut out);
+    assert_eq!(&out[..data.len()], data);
+    assert_eq!(&out[data.len()..], &passed[..]);
+});
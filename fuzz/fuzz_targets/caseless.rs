@@ -0,0 +1,56 @@
+//! Checks that caseless comparison and searching agree with comparing full case foldings.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use unicode_case_mapping::{
+    caseless_contains, caseless_ends_with, caseless_eq, caseless_find, caseless_starts_with,
+    fold_key, Caseless, CaselessMatcher,
+};
+
+/// The length of the header at the start of a fold key.
+const HEADER_LEN: usize = 5;
+
+/// The full case folding of `s`, taken from its fold key.
+fn folded(s: &str) -> String {
+    String::from_utf8(fold_key(s)[HEADER_LEN..].to_vec()).unwrap()
+}
+
+fuzz_target!(|input: (&str, &str)| {
+    let (a, b) = input;
+    let folded_a = folded(a);
+    let folded_b = folded(b);
+
+    // Full case folding is idempotent
+    assert_eq!(folded(&folded_a), folded_a);
+
+    assert_eq!(caseless_eq(a, b), folded_a == folded_b);
+    assert_eq!(caseless_eq(a, b), caseless_eq(b, a));
+    assert!(caseless_eq(a, a));
+    assert!(caseless_eq(a, &folded_a));
+    assert_eq!(Caseless(a).cmp(&Caseless(b)), folded_a.cmp(&folded_b));
+
+    let found = caseless_find(a, b);
+    assert_eq!(caseless_contains(a, b), found.is_some());
+    if let Some(range) = found.clone() {
+        assert!(caseless_eq(&a[range], b));
+    }
+    if caseless_starts_with(a, b) {
+        assert_eq!(found.map(|range| range.start), Some(0));
+    }
+    if caseless_ends_with(a, b) {
+        assert!(folded_a.ends_with(&folded_b));
+    }
+
+    let matcher = CaselessMatcher::new(&[b]);
+    let mut last_end = 0;
+    for m in matcher.find_iter(a) {
+        assert!(caseless_eq(&a[m.range()], b));
+        assert!(m.end() >= last_end);
+        last_end = m.end();
+    }
+    assert_eq!(
+        matcher.is_match(a),
+        !b.is_empty() && caseless_contains(a, b)
+    );
+});
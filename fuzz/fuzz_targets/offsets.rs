@@ -0,0 +1,59 @@
+//! Checks that offset maps cover both strings and translate offsets monotonically.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use unicode_case_mapping::{
+    lowercase_str, lowercase_with_offsets, uppercase_str, uppercase_with_offsets, OffsetMap,
+};
+
+fn check(source: &str, output: &str, offsets: &OffsetMap) {
+    // The segments cover both strings in order, splitting them on character boundaries
+    let mut source_end = 0;
+    let mut output_end = 0;
+    for segment in offsets.segments() {
+        assert_eq!(segment.source.start, source_end);
+        assert_eq!(segment.output.start, output_end);
+        assert!(source.is_char_boundary(segment.source.end));
+        assert!(output.is_char_boundary(segment.output.end));
+        source_end = segment.source.end;
+        output_end = segment.output.end;
+    }
+    assert_eq!(source_end, source.len());
+    assert_eq!(output_end, output.len());
+
+    let boundaries = |s: &str| {
+        s.char_indices()
+            .map(|(i, _)| i)
+            .chain(Some(s.len()))
+            .collect::<Vec<_>>()
+    };
+    let mut previous = 0;
+    for i in boundaries(source) {
+        let translated = offsets.to_output(i);
+        assert!(translated >= previous);
+        assert!(output.is_char_boundary(translated));
+        assert_eq!(offsets.to_source(translated), i);
+        previous = translated;
+    }
+    assert_eq!(previous, output.len());
+
+    let mut previous = 0;
+    for i in boundaries(output) {
+        let translated = offsets.to_source(i);
+        assert!(translated >= previous);
+        assert!(source.is_char_boundary(translated));
+        previous = translated;
+    }
+    assert_eq!(previous, source.len());
+}
+
+fuzz_target!(|s: &str| {
+    let (lower, offsets) = lowercase_with_offsets(s);
+    assert_eq!(lower, lowercase_str(s));
+    check(s, &lower, &offsets);
+
+    let (upper, offsets) = uppercase_with_offsets(s);
+    assert_eq!(upper, uppercase_str(s));
+    check(s, &upper, &offsets);
+});
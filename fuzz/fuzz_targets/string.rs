@@ -0,0 +1,80 @@
+//! Checks the string conversions against a simple per-character implementation.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::str;
+use unicode_case_mapping::{
+    lowercase_in_place, lowercase_str, lowercase_str_with, swapcase_str, to_lowercase,
+    to_uppercase, toggle_case, uppercase_in_place, uppercase_str, uppercase_str_with, LengthPolicy,
+};
+
+/// Map each character of `s` with `map`, without any of the fast paths.
+fn reference(s: &str, map: impl Fn(char) -> [u32; 3]) -> String {
+    let mut out = String::new();
+    for chr in s.chars() {
+        let mapped = map(chr);
+        if mapped[0] == 0 {
+            out.push(chr);
+        } else {
+            out.extend(
+                mapped
+                    .iter()
+                    .take_while(|&&cp| cp != 0)
+                    .map(|&cp| std::char::from_u32(cp).unwrap()),
+            );
+        }
+    }
+    out
+}
+
+fuzz_target!(|s: &str| {
+    let lower = lowercase_str(s);
+    let upper = uppercase_str(s);
+    let swapped = swapcase_str(s);
+    for out in &[&lower, &upper, &swapped] {
+        assert!(str::from_utf8(out.as_bytes()).is_ok());
+    }
+
+    assert_eq!(
+        lower,
+        reference(s, |chr| {
+            let [a, b] = to_lowercase(chr);
+            [a, b, 0]
+        })
+    );
+    assert_eq!(upper, reference(s, to_uppercase));
+    assert_eq!(swapped, reference(s, toggle_case));
+
+    assert_eq!(lowercase_str(&lower), lower);
+    assert_eq!(uppercase_str(&upper), upper);
+
+    let mut in_place = s.to_string();
+    lowercase_in_place(&mut in_place);
+    assert_eq!(in_place, lower);
+    let mut in_place = s.to_string();
+    uppercase_in_place(&mut in_place);
+    assert_eq!(in_place, upper);
+
+    let chars = s.chars().count();
+    assert_eq!(
+        lowercase_str_with(s, LengthPolicy::PreserveChars)
+            .chars()
+            .count(),
+        chars
+    );
+    assert_eq!(
+        uppercase_str_with(s, LengthPolicy::PreserveChars)
+            .chars()
+            .count(),
+        chars
+    );
+    assert_eq!(
+        lowercase_str_with(s, LengthPolicy::PreserveUtf8Len).len(),
+        s.len()
+    );
+    assert_eq!(
+        uppercase_str_with(s, LengthPolicy::PreserveUtf8Len).len(),
+        s.len()
+    );
+});
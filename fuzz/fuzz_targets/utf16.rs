@@ -0,0 +1,36 @@
+//! Checks the UTF-16 conversions, including unpaired surrogates.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::char;
+use unicode_case_mapping::{lowercase_str, lowercase_utf16, uppercase_str, uppercase_utf16};
+
+fn unpaired_surrogates(units: &[u16]) -> Vec<u16> {
+    char::decode_utf16(units.iter().cloned())
+        .filter_map(|result| result.err())
+        .map(|err| err.unpaired_surrogate())
+        .collect()
+}
+
+fuzz_target!(|data: &[u8]| {
+    let input: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+
+    let mut lower = Vec::new();
+    lowercase_utf16(&input, &mut lower);
+    let mut upper = Vec::new();
+    uppercase_utf16(&input, &mut upper);
+
+    // Unpaired surrogates are passed through unchanged and no new ones are introduced
+    let surrogates = unpaired_surrogates(&input);
+    assert_eq!(unpaired_surrogates(&lower), surrogates);
+    assert_eq!(unpaired_surrogates(&upper), surrogates);
+
+    if let Ok(s) = String::from_utf16(&input) {
+        assert_eq!(lower, lowercase_str(&s).encode_utf16().collect::<Vec<_>>());
+        assert_eq!(upper, uppercase_str(&s).encode_utf16().collect::<Vec<_>>());
+    }
+});
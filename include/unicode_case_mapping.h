@@ -0,0 +1,112 @@
+/* Unicode case mapping, generated from src/ffi.rs with cbindgen. Do not edit. */
+
+#ifndef UNICODE_CASE_MAPPING_H
+#define UNICODE_CASE_MAPPING_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Copy invalid UTF-8 sequences to the output unchanged.
+#define UCM_INVALID_UTF8_PASS_THROUGH 0
+
+// Replace each invalid UTF-8 sequence with U+FFFD REPLACEMENT CHARACTER.
+#define UCM_INVALID_UTF8_REPLACE 1
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Write the version of Unicode the tables were generated from to `out` as major, minor, and
+// update.
+//
+// # Safety
+//
+// `out` must point to an array of 3 `uint32_t`.
+void ucm_unicode_version(uint32_t *out);
+
+// Map a code point to its lowercase equivalent, returning the number of code points written to
+// `out`.
+//
+// Returns 0, with `out` set to all zeros, if the code point maps to itself. This includes
+// surrogates and values above U+10FFFF.
+//
+// # Safety
+//
+// `out` must point to an array of 3 `uint32_t`.
+size_t ucm_to_lowercase(uint32_t cp, uint32_t *out);
+
+// Map a code point to its uppercase equivalent, returning the number of code points written to
+// `out`.
+//
+// Returns 0, with `out` set to all zeros, if the code point maps to itself. This includes
+// surrogates and values above U+10FFFF.
+//
+// # Safety
+//
+// `out` must point to an array of 3 `uint32_t`.
+size_t ucm_to_uppercase(uint32_t cp, uint32_t *out);
+
+// Map a code point to its titlecase equivalent, returning the number of code points written to
+// `out`.
+//
+// Returns 0, with `out` set to all zeros, if the code point maps to itself. This includes
+// surrogates and values above U+10FFFF.
+//
+// # Safety
+//
+// `out` must point to an array of 3 `uint32_t`.
+size_t ucm_to_titlecase(uint32_t cp, uint32_t *out);
+
+// Returns the simple case folding of a code point, or 0 if it folds to itself.
+uint32_t ucm_case_folded(uint32_t cp);
+
+// Convert UTF-8 text to lowercase.
+//
+// Returns the length in bytes of the converted text. The text is written to `out` only if
+// `out_len` is at least this length, otherwise `out` is left untouched and the caller can
+// retry with a larger buffer. The output is not NUL terminated. `invalid_utf8` is one of the
+// `UCM_INVALID_UTF8_*` constants, other values are treated as `UCM_INVALID_UTF8_PASS_THROUGH`.
+//
+// # Safety
+//
+// `input` must point to `input_len` readable bytes and `out` to `out_len` writable bytes. Either
+// may be null if the corresponding length is 0.
+size_t ucm_lowercase_utf8(const uint8_t *input,
+                          size_t input_len,
+                          uint8_t *out,
+                          size_t out_len,
+                          uint32_t invalid_utf8);
+
+// Convert UTF-8 text to uppercase.
+//
+// The arguments and return value are as per `ucm_lowercase_utf8`.
+//
+// # Safety
+//
+// `input` must point to `input_len` readable bytes and `out` to `out_len` writable bytes. Either
+// may be null if the corresponding length is 0.
+size_t ucm_uppercase_utf8(const uint8_t *input,
+                          size_t input_len,
+                          uint8_t *out,
+                          size_t out_len,
+                          uint32_t invalid_utf8);
+
+// Apply simple case folding to UTF-8 text.
+//
+// The arguments and return value are as per `ucm_lowercase_utf8`.
+//
+// # Safety
+//
+// `input` must point to `input_len` readable bytes and `out` to `out_len` writable bytes. Either
+// may be null if the corresponding length is 0.
+size_t ucm_case_fold_utf8(const uint8_t *input,
+                          size_t input_len,
+                          uint8_t *out,
+                          size_t out_len,
+                          uint32_t invalid_utf8);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* UNICODE_CASE_MAPPING_H */
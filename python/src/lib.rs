@@ -0,0 +1,98 @@
+//! Python bindings for unicode-case-mapping, built with [PyO3] and [maturin].
+//!
+//! The module uses the same tables as the Rust crate, so results are identical to Rust code
+//! using the same version of the crate, regardless of the Unicode version of the Python
+//! interpreter. Python's own `str.lower`, `str.upper`, and `str.casefold` use the interpreter's
+//! Unicode version.
+//!
+//! [PyO3]: https://pyo3.rs/
+//! [maturin]: https://www.maturin.rs/
+
+use pyo3::prelude::*;
+
+/// Collect a mapping returned by the Rust functions into a string. All zeros means the
+/// character maps to itself.
+fn mapping_to_string(chr: char, mapping: &[u32]) -> String {
+    if mapping[0] == 0 {
+        return chr.to_string();
+    }
+    mapping
+        .iter()
+        .take_while(|&&cp| cp != 0)
+        .map(|&cp| std::char::from_u32(cp).expect("invalid code point in case mapping"))
+        .collect()
+}
+
+/// Map a character to its lowercase equivalent, which may be more than one character.
+#[pyfunction]
+fn to_lowercase(chr: char) -> String {
+    mapping_to_string(chr, &unicode_case_mapping::to_lowercase(chr))
+}
+
+/// Map a character to its uppercase equivalent, which may be more than one character.
+#[pyfunction]
+fn to_uppercase(chr: char) -> String {
+    mapping_to_string(chr, &unicode_case_mapping::to_uppercase(chr))
+}
+
+/// Map a character to its titlecase equivalent, which may be more than one character.
+#[pyfunction]
+fn to_titlecase(chr: char) -> String {
+    mapping_to_string(chr, &unicode_case_mapping::to_titlecase(chr))
+}
+
+/// Map a character to its simple case folding.
+#[pyfunction]
+fn case_folded(chr: char) -> char {
+    unicode_case_mapping::case_folded(chr)
+        .and_then(|cp| std::char::from_u32(cp.get()))
+        .unwrap_or(chr)
+}
+
+/// Convert a string to lowercase.
+#[pyfunction]
+fn lowercase(s: &str) -> String {
+    unicode_case_mapping::lowercase_str(s)
+}
+
+/// Convert a string to uppercase.
+#[pyfunction]
+fn uppercase(s: &str) -> String {
+    unicode_case_mapping::uppercase_str(s)
+}
+
+/// Convert the first letter of each whitespace separated word to titlecase and the rest to
+/// lowercase.
+#[pyfunction]
+fn titlecase(s: &str) -> String {
+    unicode_case_mapping::titlecase_str(s)
+}
+
+/// Swap the case of each character in a string.
+#[pyfunction]
+fn swapcase(s: &str) -> String {
+    unicode_case_mapping::swapcase_str(s)
+}
+
+/// Apply full case folding to a string, as `str.casefold` does.
+#[pyfunction]
+fn casefold(s: &str) -> String {
+    unicode_case_mapping::full_case_fold_str(s)
+}
+
+/// Unicode case mapping using the tables of the unicode-case-mapping Rust crate.
+#[pymodule]
+#[pyo3(name = "unicode_case_mapping")]
+fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("UNICODE_VERSION", unicode_case_mapping::UNICODE_VERSION)?;
+    m.add_function(wrap_pyfunction!(to_lowercase, m)?)?;
+    m.add_function(wrap_pyfunction!(to_uppercase, m)?)?;
+    m.add_function(wrap_pyfunction!(to_titlecase, m)?)?;
+    m.add_function(wrap_pyfunction!(case_folded, m)?)?;
+    m.add_function(wrap_pyfunction!(lowercase, m)?)?;
+    m.add_function(wrap_pyfunction!(uppercase, m)?)?;
+    m.add_function(wrap_pyfunction!(titlecase, m)?)?;
+    m.add_function(wrap_pyfunction!(swapcase, m)?)?;
+    m.add_function(wrap_pyfunction!(casefold, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,73 @@
+"""Tests of the Python bindings. Build the module with `maturin develop` and run with
+`python -m unittest discover tests`."""
+
+import sys
+import unicodedata
+import unittest
+
+import unicode_case_mapping as ucm
+
+
+def all_chars():
+    return (chr(cp) for cp in range(sys.maxunicode + 1) if not 0xD800 <= cp <= 0xDFFF)
+
+
+class CharTest(unittest.TestCase):
+    def test_to_lowercase(self):
+        self.assertEqual(ucm.to_lowercase("İ"), "i̇")
+        self.assertEqual(ucm.to_lowercase("ß"), "ß")
+
+    def test_to_uppercase(self):
+        self.assertEqual(ucm.to_uppercase("ß"), "SS")
+        self.assertEqual(ucm.to_uppercase("-"), "-")
+
+    def test_to_titlecase(self):
+        self.assertEqual(ucm.to_titlecase("ß"), "Ss")
+        self.assertEqual(ucm.to_titlecase("ǆ"), "ǅ")
+
+    def test_case_folded(self):
+        self.assertEqual(ucm.case_folded("ẞ"), "ß")
+        self.assertEqual(ucm.case_folded("ß"), "ß")
+
+    def test_rejects_strings(self):
+        with self.assertRaises(ValueError):
+            ucm.to_lowercase("AB")
+
+
+class StringTest(unittest.TestCase):
+    def test_conversions(self):
+        self.assertEqual(ucm.lowercase("HELLO İSTANBUL"), "hello i̇stanbul")
+        self.assertEqual(ucm.uppercase("Straße"), "STRASSE")
+        self.assertEqual(ucm.titlecase("ǆEMAL o'NEILL ßeta"), "ǅemal O'neill Sseta")
+        self.assertEqual(ucm.swapcase("Hello Straße ǅ"), "hELLO sTRASSE ǆ")
+        self.assertEqual(ucm.casefold("Straße ΣΊΣΥΦΟΣ"), "strasse σίσυφοσ")
+
+    def test_matches_python_for_shared_characters(self):
+        text = "Ελληνικά Русский Türkçe İı ქართული Ⴀ 日本語 😀 Straße ﬃ ŉ"
+        self.assertEqual(ucm.lowercase(text), text.lower())
+        self.assertEqual(ucm.uppercase(text), text.upper())
+        self.assertEqual(ucm.casefold(text), text.casefold())
+
+
+@unittest.skipUnless(
+    unicodedata.unidata_version == "%d.%d.%d" % ucm.UNICODE_VERSION,
+    "Python uses Unicode %s" % unicodedata.unidata_version,
+)
+class SameUnicodeVersionTest(unittest.TestCase):
+    """With the same version of Unicode the results match Python's own methods."""
+
+    def test_all_characters(self):
+        for c in all_chars():
+            self.assertEqual(ucm.lowercase(c), c.lower(), hex(ord(c)))
+            self.assertEqual(ucm.uppercase(c), c.upper(), hex(ord(c)))
+            self.assertEqual(ucm.casefold(c), c.casefold(), hex(ord(c)))
+
+
+class VersionTest(unittest.TestCase):
+    def test_unicode_version(self):
+        self.assertEqual(len(ucm.UNICODE_VERSION), 3)
+        self.assertGreaterEqual(ucm.UNICODE_VERSION, (16, 0, 0))
+
+
+if __name__ == "__main__":
+    unittest.main()
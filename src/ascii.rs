@@ -0,0 +1,55 @@
+//! SWAR (SIMD within a register) helpers for converting runs of ASCII a word at a time.
+
+use std::convert::TryFrom;
+use std::mem;
+
+/// Number of bytes processed at a time.
+pub const WORD_SIZE: usize = mem::size_of::<u64>();
+
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// Copy `byte` into every byte of a word.
+const fn repeat(byte: u8) -> u64 {
+    byte as u64 * 0x0101_0101_0101_0101
+}
+
+/// Read the first `WORD_SIZE` bytes of `bytes` as a word, if they are all ASCII.
+#[inline]
+pub fn read_ascii_word(bytes: &[u8]) -> Option<u64> {
+    let word = u64::from_ne_bytes(<[u8; WORD_SIZE]>::try_from(bytes.get(..WORD_SIZE)?).ok()?);
+    if word & HIGH_BITS == 0 {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+/// Returns a word with the high bit set in each byte of `word` that falls in `lo..=hi`.
+///
+/// Every byte of `word` must be ASCII, which ensures the additions below never carry into the
+/// neighbouring byte.
+#[inline]
+fn range_mask(word: u64, lo: u8, hi: u8) -> u64 {
+    let at_least_lo = word + repeat(0x80 - lo);
+    let above_hi = word + repeat(0x7F - hi);
+    (at_least_lo ^ above_hi) & HIGH_BITS
+}
+
+/// Lowercase each byte of an all-ASCII word.
+#[inline]
+pub fn lowercase_word(word: u64) -> u64 {
+    word | (range_mask(word, b'A', b'Z') >> 2)
+}
+
+/// Uppercase each byte of an all-ASCII word.
+#[inline]
+pub fn uppercase_word(word: u64) -> u64 {
+    word & !(range_mask(word, b'a', b'z') >> 2)
+}
+
+/// Toggle the case of each letter in an all-ASCII word.
+#[inline]
+pub fn toggle_word(word: u64) -> u64 {
+    let letters = range_mask(word, b'A', b'Z') | range_mask(word, b'a', b'z');
+    word ^ (letters >> 2)
+}
@@ -0,0 +1,247 @@
+//! `unicase-map`: convert the case of text, or inspect the case mappings of each character.
+//!
+//! Built when the `cli` feature is enabled.
+
+use std::char;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process;
+use std::str;
+
+use unicode_case_mapping::{
+    case_fold_bytes, case_folded, lowercase_bytes, to_lowercase, to_titlecase, to_uppercase,
+    uppercase_bytes, InvalidUtf8, Titlecaser,
+};
+
+const USAGE: &str = "\
+Usage: unicase-map <MODE> [FILE]...
+
+Convert the case of each FILE, or standard input, and write it to standard output.
+With no FILE, or when FILE is -, read standard input. Invalid UTF-8 is passed through
+unchanged.
+
+Modes:
+    lower      Convert to lowercase
+    upper      Convert to uppercase
+    title      Convert the first letter of each word to titlecase and the rest to lowercase
+    fold       Apply simple case folding
+    inspect    Print the case mappings and name of each character
+
+Options:
+    -h, --help       Print this help
+    -V, --version    Print the version and Unicode version";
+
+#[derive(Debug, Copy, Clone)]
+enum Mode {
+    Lower,
+    Upper,
+    Title,
+    Fold,
+    Inspect,
+}
+
+impl Mode {
+    fn from_arg(arg: &str) -> Option<Mode> {
+        match arg {
+            "lower" => Some(Mode::Lower),
+            "upper" => Some(Mode::Upper),
+            "title" => Some(Mode::Title),
+            "fold" => Some(Mode::Fold),
+            "inspect" => Some(Mode::Inspect),
+            _ => None,
+        }
+    }
+}
+
+/// An error reading the input or writing to standard output.
+enum Error {
+    Read(io::Error),
+    Write(io::Error),
+}
+
+/// Converts input line by line, so that output appears promptly in a pipeline.
+struct Converter<W: Write> {
+    mode: Mode,
+    out: W,
+    buf: Vec<u8>,
+    /// Carries the start of words across lines, used by `Mode::Title`
+    titlecaser: Titlecaser,
+    title: String,
+}
+
+impl<W: Write> Converter<W> {
+    fn new(mode: Mode, out: W) -> Self {
+        Converter {
+            mode,
+            out,
+            buf: Vec::new(),
+            titlecaser: Titlecaser::new(),
+            title: String::new(),
+        }
+    }
+
+    fn convert<R: BufRead>(&mut self, mut input: R) -> Result<(), Error> {
+        let mut line = Vec::new();
+        while input.read_until(b'\n', &mut line).map_err(Error::Read)? > 0 {
+            self.buf.clear();
+            match self.mode {
+                Mode::Lower => lowercase_bytes(&line, InvalidUtf8::PassThrough, &mut self.buf),
+                Mode::Upper => uppercase_bytes(&line, InvalidUtf8::PassThrough, &mut self.buf),
+                Mode::Fold => case_fold_bytes(&line, InvalidUtf8::PassThrough, &mut self.buf),
+                Mode::Title => self.titlecase(&line),
+                Mode::Inspect => self.inspect(&line).map_err(Error::Write)?,
+            }
+            self.out.write_all(&self.buf).map_err(Error::Write)?;
+            line.clear();
+        }
+        self.out.flush().map_err(Error::Write)
+    }
+
+    fn titlecase(&mut self, mut line: &[u8]) {
+        while !line.is_empty() {
+            let (valid, invalid) = split_valid(line);
+            self.title.clear();
+            self.titlecaser.convert(valid, &mut self.title);
+            self.buf.extend_from_slice(self.title.as_bytes());
+            self.buf.extend_from_slice(invalid);
+            line = &line[valid.len() + invalid.len()..];
+        }
+    }
+
+    fn inspect(&mut self, mut line: &[u8]) -> io::Result<()> {
+        while !line.is_empty() {
+            let (valid, invalid) = split_valid(line);
+            for chr in valid.chars() {
+                writeln!(self.out, "{}", describe(chr))?;
+                let lower = to_lowercase(chr);
+                let folded = case_folded(chr).map_or(0, |cp| cp.get());
+                let mappings = [
+                    ("to_lowercase", &lower[..]),
+                    ("to_uppercase", &to_uppercase(chr)[..]),
+                    ("to_titlecase", &to_titlecase(chr)[..]),
+                    ("case_folded", &[folded][..]),
+                ];
+                for &(function, mapped) in &mappings {
+                    let mapped: Vec<String> = mapped
+                        .iter()
+                        .take_while(|&&cp| cp != 0)
+                        .map(|&cp| describe_u32(cp))
+                        .collect();
+                    if mapped.is_empty() {
+                        writeln!(self.out, "    {:<13} (maps to itself)", function)?;
+                    } else {
+                        writeln!(self.out, "    {:<13} {}", function, mapped.join(", "))?;
+                    }
+                }
+            }
+            for byte in invalid {
+                writeln!(self.out, "0x{:02X} (invalid UTF-8)", byte)?;
+            }
+            line = &line[valid.len() + invalid.len()..];
+        }
+        Ok(())
+    }
+}
+
+/// Split `bytes` into its longest valid UTF-8 prefix and the invalid sequence that follows it.
+fn split_valid(bytes: &[u8]) -> (&str, &[u8]) {
+    match str::from_utf8(bytes) {
+        Ok(valid) => (valid, &[]),
+        Err(err) => {
+            let valid_len = err.valid_up_to();
+            let invalid_len = err.error_len().unwrap_or(bytes.len() - valid_len);
+            // SAFETY: `from_utf8` checked that the bytes up to `valid_len` are valid UTF-8.
+            let valid = unsafe { str::from_utf8_unchecked(&bytes[..valid_len]) };
+            (valid, &bytes[valid_len..valid_len + invalid_len])
+        }
+    }
+}
+
+/// Describe a character as its code point, the character itself if printable, and its name.
+fn describe(chr: char) -> String {
+    let name = unicode_names2::name(chr)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| "<unnamed>".to_string());
+    if chr.is_control() || chr.is_whitespace() {
+        format!("U+{:04X} {}", chr as u32, name)
+    } else {
+        format!("U+{:04X} '{}' {}", chr as u32, chr, name)
+    }
+}
+
+fn describe_u32(cp: u32) -> String {
+    char::from_u32(cp).map_or_else(|| format!("U+{:04X}", cp), describe)
+}
+
+fn run(mode: Mode, paths: &[String]) -> Result<(), (String, io::Error)> {
+    let stdout = io::stdout();
+    let mut converter = Converter::new(mode, stdout.lock());
+    if paths.is_empty() {
+        return convert_stdin(&mut converter).map_err(|err| error_source("<stdin>", err));
+    }
+    for path in paths {
+        let result = if path == "-" {
+            convert_stdin(&mut converter)
+        } else {
+            File::open(path)
+                .map_err(Error::Read)
+                .and_then(|file| converter.convert(BufReader::new(file)))
+        };
+        result.map_err(|err| error_source(path, err))?;
+    }
+    Ok(())
+}
+
+/// Attribute an error to the input it was read from, or to standard output.
+fn error_source(input: &str, err: Error) -> (String, io::Error) {
+    match err {
+        Error::Read(err) => (input.to_string(), err),
+        Error::Write(err) => ("<stdout>".to_string(), err),
+    }
+}
+
+fn convert_stdin<W: Write>(converter: &mut Converter<W>) -> Result<(), Error> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    converter.convert(&mut input)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mode = match args.first().map(String::as_str) {
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            return;
+        }
+        Some("-V") | Some("--version") => {
+            let (major, minor, update) = unicode_case_mapping::UNICODE_VERSION;
+            println!(
+                "unicase-map {} (Unicode {}.{}.{})",
+                env!("CARGO_PKG_VERSION"),
+                major,
+                minor,
+                update
+            );
+            return;
+        }
+        Some(arg) => Mode::from_arg(arg),
+        None => None,
+    };
+    let mode = match mode {
+        Some(mode) => mode,
+        None => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err((path, err)) = run(mode, &args[1..]) {
+        // Stop quietly when the reader of a pipeline exits early, as with `| head`
+        if err.kind() == io::ErrorKind::BrokenPipe {
+            return;
+        }
+        eprintln!("unicase-map: {}: {}", path, err);
+        process::exit(1);
+    }
+}
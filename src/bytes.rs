@@ -0,0 +1,114 @@
+use std::str;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::case_mapping::{Builtin, Tables};
+use crate::string::{convert_str_into, Case, LengthPolicy};
+
+/// The UTF-8 encoding of U+FFFD REPLACEMENT CHARACTER.
+const REPLACEMENT: &[u8] = b"\xEF\xBF\xBD";
+
+/// How the byte-level conversion functions handle invalid UTF-8.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InvalidUtf8 {
+    /// Copy invalid sequences to the output unchanged.
+    PassThrough,
+    /// Replace each invalid sequence with U+FFFD REPLACEMENT CHARACTER, in the same manner as
+    /// `String::from_utf8_lossy`.
+    Replace,
+}
+
+/// Convert possibly invalid UTF-8 to lowercase, appending the result to `out`.
+///
+/// Valid characters are mapped as per [`to_lowercase`]. Invalid sequences are handled according
+/// to `mode`.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::InvalidUtf8;
+///
+/// let mut out = Vec::new();
+/// unicode_case_mapping::lowercase_bytes(b"ABC\xFFD\xC3\x89", InvalidUtf8::PassThrough, &mut out);
+/// assert_eq!(out, b"abc\xFFd\xC3\xA9");
+/// ```
+///
+/// [`to_lowercase`]: fn.to_lowercase.html
+pub fn lowercase_bytes(input: &[u8], mode: InvalidUtf8, out: &mut Vec<u8>) {
+    convert_bytes(&Builtin, input, mode, out, Case::Lower)
+}
+
+/// Convert possibly invalid UTF-8 to uppercase, appending the result to `out`.
+///
+/// Valid characters are mapped as per [`to_uppercase`]. Invalid sequences are handled according
+/// to `mode`.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::InvalidUtf8;
+///
+/// let mut out = Vec::new();
+/// unicode_case_mapping::uppercase_bytes(b"stra\xC3\x9Fe\xE2\x82", InvalidUtf8::Replace, &mut out);
+/// assert_eq!(out, "STRASSE\u{FFFD}".as_bytes());
+/// ```
+///
+/// [`to_uppercase`]: fn.to_uppercase.html
+pub fn uppercase_bytes(input: &[u8], mode: InvalidUtf8, out: &mut Vec<u8>) {
+    convert_bytes(&Builtin, input, mode, out, Case::Upper)
+}
+
+/// Case fold possibly invalid UTF-8, appending the result to `out`.
+///
+/// Valid characters are mapped as per [`case_folded`]. Invalid sequences are handled according
+/// to `mode`.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::InvalidUtf8;
+///
+/// let mut out = Vec::new();
+/// unicode_case_mapping::case_fold_bytes(b"\xE1\xBA\x9E\x80K", InvalidUtf8::Replace, &mut out);
+/// assert_eq!(out, "ß\u{FFFD}k".as_bytes());
+/// ```
+///
+/// [`case_folded`]: fn.case_folded.html
+pub fn case_fold_bytes(input: &[u8], mode: InvalidUtf8, out: &mut Vec<u8>) {
+    convert_bytes(&Builtin, input, mode, out, Case::Fold)
+}
+
+pub(crate) fn convert_bytes<T: Tables>(
+    tables: &T,
+    mut input: &[u8],
+    mode: InvalidUtf8,
+    out: &mut Vec<u8>,
+    case: Case,
+) {
+    out.reserve(input.len());
+    while !input.is_empty() {
+        let (valid, invalid_len) = match str::from_utf8(input) {
+            Ok(valid) => (valid, 0),
+            Err(err) => {
+                let (valid, rest) = input.split_at(err.valid_up_to());
+                // An error without a length is a truncated sequence at the end of the input
+                let invalid_len = err.error_len().unwrap_or(rest.len());
+                // SAFETY: `valid_up_to` is the length of the prefix that is valid UTF-8
+                (unsafe { str::from_utf8_unchecked(valid) }, invalid_len)
+            }
+        };
+        convert_str_into(tables, valid, out, case, LengthPolicy::Unrestricted);
+        input = &input[valid.len()..];
+
+        let (invalid, rest) = input.split_at(invalid_len);
+        if !invalid.is_empty() {
+            match mode {
+                InvalidUtf8::PassThrough => out.extend_from_slice(invalid),
+                InvalidUtf8::Replace => out.extend_from_slice(REPLACEMENT),
+            }
+        }
+        input = rest;
+    }
+}
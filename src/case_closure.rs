@@ -0,0 +1,196 @@
+use std::char;
+use std::cmp::Ordering;
+use std::ops::{Deref, RangeInclusive};
+use std::slice;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::case_mapping::case_folded;
+
+include!(concat!(env!("OUT_DIR"), "/case_folding_inverse.rs")); // generated by build.rs
+
+/// Returns all the characters that case fold to the supplied character.
+///
+/// This is the inverse of [`case_folded`]. If any characters fold to `chr`, the sorted result
+/// includes `chr` itself; otherwise it is empty. This is the case for characters without case,
+/// and for characters like `K` that are not themselves in case folded form.
+///
+/// ### Example
+///
+/// ```
+/// // U+212A is KELVIN SIGN
+/// assert_eq!(unicode_case_mapping::chars_folding_to('k'), &['K', 'k', '\u{212A}']);
+/// assert_eq!(unicode_case_mapping::chars_folding_to('K'), &[]);
+/// assert_eq!(unicode_case_mapping::chars_folding_to('-'), &[]);
+/// ```
+///
+/// [`case_folded`]: fn.case_folded.html
+pub fn chars_folding_to(chr: char) -> &'static [char] {
+    let cp = chr as u32;
+    CASE_FOLDING_INVERSE
+        .binary_search_by(|&(folded, _)| folded.cmp(&cp))
+        .map(|index| CASE_FOLDING_INVERSE[index].1)
+        .unwrap_or(&[])
+}
+
+/// Returns the equivalence class of the supplied character under simple case folding.
+///
+/// The result is all the characters that case fold to the same character as `chr`, sorted, and
+/// always includes `chr` itself.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(&*unicode_case_mapping::case_closure('K'), &['K', 'k', '\u{212A}']);
+/// assert_eq!(&*unicode_case_mapping::case_closure('ϑ'), &['Θ', 'θ', 'ϑ', 'ϴ']);
+/// assert_eq!(&*unicode_case_mapping::case_closure('-'), &['-']);
+/// ```
+pub fn case_closure(chr: char) -> CaseClosure<'static> {
+    let folded = case_folded(chr)
+        .and_then(|folded| char::from_u32(folded.get()))
+        .unwrap_or(chr);
+    CaseClosure::new(chr, chars_folding_to(folded))
+}
+
+/// Expand a set of character ranges to include all case variants under simple case folding.
+///
+/// The result contains every character in `ranges`, plus every character that is equivalent to
+/// one of them according to [`case_closure`]. It is sorted, and overlapping or adjacent ranges
+/// are merged. This is suitable for compiling case-insensitive character classes in regex or
+/// glob engines.
+///
+/// ### Example
+///
+/// ```
+/// let ranges = unicode_case_mapping::case_fold_ranges(&['a'..='f', 'j'..='k']);
+/// assert_eq!(ranges, vec!['A'..='F', 'J'..='K', 'a'..='f', 'j'..='k', '\u{212A}'..='\u{212A}']);
+/// ```
+///
+/// [`case_closure`]: fn.case_closure.html
+pub fn case_fold_ranges(ranges: &[RangeInclusive<char>]) -> Vec<RangeInclusive<char>> {
+    expand_ranges(ranges, CASE_FOLDING_INVERSE.iter().map(|&(_, chars)| chars))
+}
+
+/// Expand `ranges` as per `case_fold_ranges`, where `classes` are the characters that fold to each
+/// folded character.
+pub(crate) fn expand_ranges<'a, I>(
+    ranges: &[RangeInclusive<char>],
+    classes: I,
+) -> Vec<RangeInclusive<char>>
+where
+    I: Iterator<Item = &'a [char]>,
+{
+    let ranges = merge_ranges(ranges.to_vec());
+    let contains = |chr: char| {
+        ranges
+            .binary_search_by(|range| {
+                if *range.end() < chr {
+                    Ordering::Less
+                } else if *range.start() > chr {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+    };
+
+    let mut expanded = ranges.clone();
+    for chars in classes {
+        if chars.iter().any(|&chr| contains(chr)) {
+            expanded.extend(chars.iter().map(|&chr| chr..=chr));
+        }
+    }
+    merge_ranges(expanded)
+}
+
+/// Sort `ranges` and merge any that overlap or are adjacent.
+fn merge_ranges(mut ranges: Vec<RangeInclusive<char>>) -> Vec<RangeInclusive<char>> {
+    ranges.retain(|range| range.start() <= range.end());
+    ranges.sort_by_key(|range| *range.start());
+
+    let mut merged: Vec<RangeInclusive<char>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if let Some(last) = merged.last_mut() {
+            if next_char(*last.end()).map_or(true, |next| next >= *range.start()) {
+                if range.end() > last.end() {
+                    *last = *last.start()..=*range.end();
+                }
+                continue;
+            }
+        }
+        merged.push(range);
+    }
+    merged
+}
+
+/// Returns the character after `chr`, skipping over the surrogate code points.
+fn next_char(chr: char) -> Option<char> {
+    match chr {
+        '\u{D7FF}' => Some('\u{E000}'),
+        _ => char::from_u32(chr as u32 + 1),
+    }
+}
+
+/// The equivalence class of a character under simple case folding.
+///
+/// Returned by [`case_closure`] and [`CaseData::case_closure`]. Dereferences to a sorted slice of
+/// the characters in the class.
+///
+/// [`case_closure`]: fn.case_closure.html
+/// [`CaseData::case_closure`]: struct.CaseData.html#method.case_closure
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CaseClosure<'a> {
+    chr: [char; 1],
+    chars: &'a [char],
+}
+
+impl<'a> CaseClosure<'a> {
+    /// `chars` are the characters that fold to the same character as `chr`, or empty if there
+    /// are none.
+    pub(crate) fn new(chr: char, chars: &'a [char]) -> Self {
+        CaseClosure { chr: [chr], chars }
+    }
+}
+
+impl Deref for CaseClosure<'_> {
+    type Target = [char];
+
+    fn deref(&self) -> &[char] {
+        if self.chars.is_empty() {
+            &self.chr
+        } else {
+            self.chars
+        }
+    }
+}
+
+/// Serialized as the sequence of characters. Deserializing checks that they are exactly the case
+/// closure of the first character.
+#[cfg(feature = "serde")]
+impl Serialize for CaseClosure<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CaseClosure<'static> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let chars = Vec::<char>::deserialize(deserializer)?;
+        match chars.first() {
+            Some(&chr) if *case_closure(chr) == chars[..] => Ok(case_closure(chr)),
+            _ => Err(de::Error::custom("characters are not a case closure")),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a CaseClosure<'_> {
+    type Item = &'a char;
+    type IntoIter = slice::Iter<'a, char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,755 @@
+use std::char;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroU32;
+use std::ops::{Range, RangeInclusive};
+
+use crate::bytes::{convert_bytes, InvalidUtf8};
+use crate::case_closure::{expand_ranges, CaseClosure};
+use crate::case_folding_simple::case_folding_simple;
+use crate::case_mapping::{
+    simple_mapping, toggle, Row, Tables, BLOCK_SIZE, CASE_MAPPING_BLOCKS,
+    CASE_MAPPING_BLOCK_OFFSETS, CASE_MAPPING_RECORDS, LAST_CODEPOINT, SIMPLE_CASE_MAPPINGS,
+};
+use crate::caseless::{ends_with_folded, find_folded, folded_eq, starts_with_folded};
+use crate::fold_key::make_fold_key;
+use crate::offsets::{convert_with_offsets, OffsetMap};
+use crate::string::{
+    convert_in_place, convert_str, convert_str_in_place, convert_titlecase, Case, LengthPolicy,
+};
+use crate::utf16::convert_utf16;
+use crate::UNICODE_VERSION;
+
+/// The first bytes of every case data file.
+const MAGIC: &[u8; 8] = b"UCMDATA\0";
+
+/// The version of the case data layout, incremented if the layout changes.
+const FORMAT_VERSION: u16 = 1;
+
+const HEADER_LEN: usize = 40;
+const CHECKSUM_LEN: usize = 4;
+
+/// The size in bytes of each entry of the sections that follow the header.
+const RECORD_LEN: usize = 8 * 4;
+const SIMPLE_MAPPING_LEN: usize = 4 * 4;
+const CASE_FOLDING_LEN: usize = 2 * 4;
+const BLOCK_ENTRY_LEN: usize = 2;
+const BLOCK_OFFSET_LEN: usize = 2;
+
+const NO_MAPPING: Row = ([0; 2], [0; 3], [0; 3]);
+
+/// Case mapping data that can be loaded at run time.
+///
+/// The free functions of this crate use tables compiled into the binary, so updating the Unicode
+/// version requires rebuilding. A `CaseData` holds the same tables, and can be written to a file
+/// with [`to_bytes`] by a build of this crate with newer data and loaded with [`from_bytes`] by a
+/// long-running service, which might keep it in an `Arc` to swap in new data without restarting.
+/// Its methods mirror the free functions of the same name, including the string conversions and
+/// caseless comparisons. Types such as [`CaselessMatcher`], [`Caseless`], [`FoldKeyWriter`], and
+/// [`Titlecaser`] always use the built-in tables.
+///
+/// ### Format
+///
+/// All integers are little endian. The file begins with a 40 byte header:
+///
+/// | Offset | Size | Contents                                                     |
+/// |-------:|-----:|--------------------------------------------------------------|
+/// |      0 |    8 | `UCMDATA\0`                                                  |
+/// |      8 |    2 | Format version, currently 1                                  |
+/// |     10 |    3 | Unicode version: major, minor, update                        |
+/// |     13 |    1 | log₂ of the block size                                       |
+/// |     14 |    2 | Reserved, 0                                                  |
+/// |     16 |    4 | The last code point with a mapping                           |
+/// |     20 |    4 | Number of records                                            |
+/// |     24 |    4 | Number of simple mappings                                    |
+/// |     28 |    4 | Number of case foldings                                      |
+/// |     32 |    4 | Number of block entries                                      |
+/// |     36 |    4 | Number of block offsets                                      |
+///
+/// The sections follow in that order: records of 8 `u32` (lowercase, uppercase, and titlecase
+/// mappings of 2, 3, and 3 code points), simple mappings of 4 `u32` (code point, then simple
+/// lowercase, uppercase, and titlecase), case foldings of 2 `u32` (code point, folded code point),
+/// then `u16` block entries and `u16` block offsets. The file ends with the CRC-32 (as used by
+/// zlib) of all of the preceding bytes.
+///
+/// A code point is looked up by using its high bits to index the block offsets, then adding its
+/// low bits to get the index of a block entry, which is the index of its record.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::CaseData;
+///
+/// let bytes = CaseData::builtin().to_bytes();
+/// let data = CaseData::from_bytes(&bytes)?;
+/// assert_eq!(data.unicode_version(), unicode_case_mapping::UNICODE_VERSION);
+/// assert_eq!(data.to_uppercase('ß'), ['S' as u32, 'S' as u32, 0]);
+/// assert_eq!(data.simple_titlecase('ǆ'), 'ǅ');
+/// assert_eq!(data.uppercase_str("Straße"), "STRASSE");
+/// assert!(data.caseless_eq("Straße", "STRASSE"));
+///
+/// assert!(CaseData::from_bytes(&bytes[1..]).is_err());
+/// # Ok::<(), unicode_case_mapping::CaseDataError>(())
+/// ```
+///
+/// [`to_bytes`]: #method.to_bytes
+/// [`from_bytes`]: #method.from_bytes
+/// [`CaselessMatcher`]: struct.CaselessMatcher.html
+/// [`Caseless`]: struct.Caseless.html
+/// [`FoldKeyWriter`]: struct.FoldKeyWriter.html
+/// [`Titlecaser`]: struct.Titlecaser.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseData {
+    unicode_version: (u8, u8, u8),
+    shift: u32,
+    last_codepoint: u32,
+    records: Vec<Row>,
+    simple_mappings: Vec<(u32, [u32; 3])>,
+    case_folding: Vec<(u32, u32)>,
+    blocks: Vec<u16>,
+    block_offsets: Vec<u16>,
+    /// The characters that fold to each folded character, derived from `case_folding`
+    folding_inverse: Vec<(u32, Vec<char>)>,
+}
+
+/// The error returned by [`CaseData::from_bytes`] when the data can't be loaded.
+///
+/// [`CaseData::from_bytes`]: struct.CaseData.html#method.from_bytes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CaseDataError {
+    /// The data does not begin with the expected header.
+    NotCaseData,
+    /// The data was written in an unsupported format version.
+    UnsupportedFormat(u16),
+    /// The data is shorter or longer than the header says it should be.
+    InvalidLength,
+    /// The checksum does not match the data.
+    ChecksumMismatch,
+    /// The tables are inconsistent, for example a block entry refers to a record that doesn't
+    /// exist.
+    InvalidTables,
+}
+
+impl fmt::Display for CaseDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            CaseDataError::NotCaseData => f.write_str("not case data"),
+            CaseDataError::UnsupportedFormat(version) => {
+                write!(f, "unsupported case data format version {}", version)
+            }
+            CaseDataError::InvalidLength => f.write_str("case data has an invalid length"),
+            CaseDataError::ChecksumMismatch => f.write_str("case data checksum does not match"),
+            CaseDataError::InvalidTables => f.write_str("case data tables are inconsistent"),
+        }
+    }
+}
+
+impl Error for CaseDataError {}
+
+impl CaseData {
+    /// Returns a copy of the tables compiled into this crate.
+    pub fn builtin() -> Self {
+        let (major, minor, update) = UNICODE_VERSION;
+        let case_folding: Vec<(u32, u32)> = (0..=char::MAX as u32)
+            .filter_map(|cp| case_folding_simple(cp).map(|folded| (cp, folded.get())))
+            .collect();
+        CaseData {
+            unicode_version: (major as u8, minor as u8, update as u8),
+            shift: BLOCK_SIZE.trailing_zeros(),
+            last_codepoint: LAST_CODEPOINT,
+            records: CASE_MAPPING_RECORDS.to_vec(),
+            simple_mappings: SIMPLE_CASE_MAPPINGS.to_vec(),
+            folding_inverse: invert(&case_folding),
+            case_folding,
+            blocks: CASE_MAPPING_BLOCKS.to_vec(),
+            block_offsets: CASE_MAPPING_BLOCK_OFFSETS.to_vec(),
+        }
+    }
+
+    /// Load data written by [`to_bytes`].
+    ///
+    /// The header, checksum, and consistency of the tables are checked, so that data that is
+    /// truncated or corrupt is rejected rather than producing incorrect mappings.
+    ///
+    /// [`to_bytes`]: #method.to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CaseDataError> {
+        if bytes.len() < HEADER_LEN + CHECKSUM_LEN || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(CaseDataError::NotCaseData);
+        }
+        let mut reader = Reader {
+            bytes,
+            pos: MAGIC.len(),
+        };
+        let format_version = reader.u16();
+        if format_version != FORMAT_VERSION {
+            return Err(CaseDataError::UnsupportedFormat(format_version));
+        }
+        let unicode_version = (reader.u8(), reader.u8(), reader.u8());
+        let shift = u32::from(reader.u8());
+        let _reserved = reader.u16();
+        let last_codepoint = reader.u32();
+        let counts = [
+            (reader.u32(), RECORD_LEN),
+            (reader.u32(), SIMPLE_MAPPING_LEN),
+            (reader.u32(), CASE_FOLDING_LEN),
+            (reader.u32(), BLOCK_ENTRY_LEN),
+            (reader.u32(), BLOCK_OFFSET_LEN),
+        ];
+        let expected_len =
+            counts
+                .iter()
+                .try_fold(HEADER_LEN + CHECKSUM_LEN, |len, &(count, size)| {
+                    usize::try_from(count)
+                        .ok()
+                        .and_then(|count| count.checked_mul(size))
+                        .and_then(|size| len.checked_add(size))
+                });
+        if expected_len != Some(bytes.len()) {
+            return Err(CaseDataError::InvalidLength);
+        }
+        let (data, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if crc32(data) != Reader::new(checksum).u32() {
+            return Err(CaseDataError::ChecksumMismatch);
+        }
+
+        let records = (0..counts[0].0)
+            .map(|_| {
+                (
+                    [reader.u32(), reader.u32()],
+                    [reader.u32(), reader.u32(), reader.u32()],
+                    [reader.u32(), reader.u32(), reader.u32()],
+                )
+            })
+            .collect();
+        let simple_mappings = (0..counts[1].0)
+            .map(|_| (reader.u32(), [reader.u32(), reader.u32(), reader.u32()]))
+            .collect();
+        let case_folding = (0..counts[2].0)
+            .map(|_| (reader.u32(), reader.u32()))
+            .collect();
+        let blocks = (0..counts[3].0).map(|_| reader.u16()).collect();
+        let block_offsets = (0..counts[4].0).map(|_| reader.u16()).collect();
+
+        let mut data = CaseData {
+            unicode_version,
+            shift,
+            last_codepoint,
+            records,
+            simple_mappings,
+            case_folding,
+            blocks,
+            block_offsets,
+            folding_inverse: Vec::new(),
+        };
+        if data.is_valid() {
+            data.folding_inverse = invert(&data.case_folding);
+            Ok(data)
+        } else {
+            Err(CaseDataError::InvalidTables)
+        }
+    }
+
+    /// Serialize the data in the format read by [`from_bytes`].
+    ///
+    /// [`from_bytes`]: #method.from_bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            HEADER_LEN
+                + self.records.len() * RECORD_LEN
+                + self.simple_mappings.len() * SIMPLE_MAPPING_LEN
+                + self.case_folding.len() * CASE_FOLDING_LEN
+                + self.blocks.len() * BLOCK_ENTRY_LEN
+                + self.block_offsets.len() * BLOCK_OFFSET_LEN
+                + CHECKSUM_LEN,
+        );
+        let (major, minor, update) = self.unicode_version;
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&[major, minor, update, self.shift as u8, 0, 0]);
+        for &value in &[
+            self.last_codepoint,
+            self.records.len() as u32,
+            self.simple_mappings.len() as u32,
+            self.case_folding.len() as u32,
+            self.blocks.len() as u32,
+            self.block_offsets.len() as u32,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut push_u32s = |values: &[u32]| {
+            for value in values {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        };
+        for (lower, upper, title) in &self.records {
+            push_u32s(lower);
+            push_u32s(upper);
+            push_u32s(title);
+        }
+        for &(cp, simple) in &self.simple_mappings {
+            push_u32s(&[cp, simple[0], simple[1], simple[2]]);
+        }
+        for &(cp, folded) in &self.case_folding {
+            push_u32s(&[cp, folded]);
+        }
+        for value in self.blocks.iter().chain(self.block_offsets.iter()) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// The version of Unicode the data was generated from.
+    pub fn unicode_version(&self) -> (u64, u64, u64) {
+        let (major, minor, update) = self.unicode_version;
+        (u64::from(major), u64::from(minor), u64::from(update))
+    }
+
+    /// Map the supplied character to its lowercase equivalent, as per [`to_lowercase`].
+    ///
+    /// [`to_lowercase`]: fn.to_lowercase.html
+    pub fn to_lowercase(&self, chr: char) -> [u32; 2] {
+        self.lookup(chr as u32).0
+    }
+
+    /// Map the supplied character to its uppercase equivalent, as per [`to_uppercase`].
+    ///
+    /// [`to_uppercase`]: fn.to_uppercase.html
+    pub fn to_uppercase(&self, chr: char) -> [u32; 3] {
+        self.lookup(chr as u32).1
+    }
+
+    /// Map the supplied character to its titlecase equivalent, as per [`to_titlecase`].
+    ///
+    /// [`to_titlecase`]: fn.to_titlecase.html
+    pub fn to_titlecase(&self, chr: char) -> [u32; 3] {
+        self.lookup(chr as u32).2
+    }
+
+    /// Map the supplied character to its case-folded equivalent, as per [`case_folded`].
+    ///
+    /// [`case_folded`]: fn.case_folded.html
+    pub fn case_folded(&self, chr: char) -> Option<NonZeroU32> {
+        self.case_folded_u32(chr as u32)
+    }
+
+    /// Map the supplied character to its simple lowercase equivalent, as per
+    /// [`simple_lowercase`].
+    ///
+    /// [`simple_lowercase`]: fn.simple_lowercase.html
+    pub fn simple_lowercase(&self, chr: char) -> char {
+        simple_mapping(self, chr, 0)
+    }
+
+    /// Map the supplied character to its simple uppercase equivalent, as per
+    /// [`simple_uppercase`].
+    ///
+    /// [`simple_uppercase`]: fn.simple_uppercase.html
+    pub fn simple_uppercase(&self, chr: char) -> char {
+        simple_mapping(self, chr, 1)
+    }
+
+    /// Map the supplied character to its simple titlecase equivalent, as per
+    /// [`simple_titlecase`].
+    ///
+    /// [`simple_titlecase`]: fn.simple_titlecase.html
+    pub fn simple_titlecase(&self, chr: char) -> char {
+        simple_mapping(self, chr, 2)
+    }
+
+    /// Map the supplied character to the opposite case, as per [`toggle_case`].
+    ///
+    /// [`toggle_case`]: fn.toggle_case.html
+    pub fn toggle_case(&self, chr: char) -> [u32; 3] {
+        toggle(self.lookup(chr as u32))
+    }
+
+    /// Map the supplied code point to its lowercase equivalent, as per [`to_lowercase_u32`].
+    ///
+    /// [`to_lowercase_u32`]: fn.to_lowercase_u32.html
+    pub fn to_lowercase_u32(&self, cp: u32) -> [u32; 2] {
+        self.lookup(cp).0
+    }
+
+    /// Map the supplied code point to its uppercase equivalent, as per [`to_uppercase_u32`].
+    ///
+    /// [`to_uppercase_u32`]: fn.to_uppercase_u32.html
+    pub fn to_uppercase_u32(&self, cp: u32) -> [u32; 3] {
+        self.lookup(cp).1
+    }
+
+    /// Map the supplied code point to its titlecase equivalent, as per [`to_titlecase_u32`].
+    ///
+    /// [`to_titlecase_u32`]: fn.to_titlecase_u32.html
+    pub fn to_titlecase_u32(&self, cp: u32) -> [u32; 3] {
+        self.lookup(cp).2
+    }
+
+    /// Map the supplied code point to its case-folded equivalent, as per [`case_folded_u32`].
+    ///
+    /// [`case_folded_u32`]: fn.case_folded_u32.html
+    pub fn case_folded_u32(&self, cp: u32) -> Option<NonZeroU32> {
+        self.case_folding
+            .binary_search_by_key(&cp, |&(from, _)| from)
+            .ok()
+            .and_then(|index| NonZeroU32::new(self.case_folding[index].1))
+    }
+
+    /// Returns all the characters that case fold to the supplied character, as per
+    /// [`chars_folding_to`].
+    ///
+    /// [`chars_folding_to`]: fn.chars_folding_to.html
+    pub fn chars_folding_to(&self, chr: char) -> &[char] {
+        let cp = chr as u32;
+        self.folding_inverse
+            .binary_search_by_key(&cp, |&(folded, _)| folded)
+            .map(|index| &self.folding_inverse[index].1[..])
+            .unwrap_or(&[])
+    }
+
+    /// Returns the equivalence class of the supplied character under simple case folding, as per
+    /// [`case_closure`].
+    ///
+    /// [`case_closure`]: fn.case_closure.html
+    pub fn case_closure(&self, chr: char) -> CaseClosure<'_> {
+        let folded = self
+            .case_folded(chr)
+            .and_then(|folded| char::from_u32(folded.get()))
+            .unwrap_or(chr);
+        CaseClosure::new(chr, self.chars_folding_to(folded))
+    }
+
+    /// Expand a set of character ranges to include all case variants under simple case folding,
+    /// as per [`case_fold_ranges`].
+    ///
+    /// [`case_fold_ranges`]: fn.case_fold_ranges.html
+    pub fn case_fold_ranges(&self, ranges: &[RangeInclusive<char>]) -> Vec<RangeInclusive<char>> {
+        expand_ranges(
+            ranges,
+            self.folding_inverse.iter().map(|(_, chars)| &chars[..]),
+        )
+    }
+
+    /// Convert a string to lowercase, as per [`lowercase_str`].
+    ///
+    /// [`lowercase_str`]: fn.lowercase_str.html
+    pub fn lowercase_str(&self, s: &str) -> String {
+        convert_str(self, s, Case::Lower, LengthPolicy::Unrestricted)
+    }
+
+    /// Convert a string to uppercase, as per [`uppercase_str`].
+    ///
+    /// [`uppercase_str`]: fn.uppercase_str.html
+    pub fn uppercase_str(&self, s: &str) -> String {
+        convert_str(self, s, Case::Upper, LengthPolicy::Unrestricted)
+    }
+
+    /// Convert a string to lowercase, restricting changes to its length according to `policy`,
+    /// as per [`lowercase_str_with`].
+    ///
+    /// [`lowercase_str_with`]: fn.lowercase_str_with.html
+    pub fn lowercase_str_with(&self, s: &str, policy: LengthPolicy) -> String {
+        convert_str(self, s, Case::Lower, policy)
+    }
+
+    /// Convert a string to uppercase, restricting changes to its length according to `policy`,
+    /// as per [`uppercase_str_with`].
+    ///
+    /// [`uppercase_str_with`]: fn.uppercase_str_with.html
+    pub fn uppercase_str_with(&self, s: &str, policy: LengthPolicy) -> String {
+        convert_str(self, s, Case::Upper, policy)
+    }
+
+    /// Convert the first letter of each word in a string to titlecase and the rest to lowercase,
+    /// as per [`titlecase_str`].
+    ///
+    /// [`titlecase_str`]: fn.titlecase_str.html
+    pub fn titlecase_str(&self, s: &str) -> String {
+        convert_titlecase(self, s)
+    }
+
+    /// Swap the case of each character in a string, as per [`swapcase_str`].
+    ///
+    /// [`swapcase_str`]: fn.swapcase_str.html
+    pub fn swapcase_str(&self, s: &str) -> String {
+        convert_str(self, s, Case::Toggle, LengthPolicy::Unrestricted)
+    }
+
+    /// Apply full case folding to a string, as per [`full_case_fold_str`].
+    ///
+    /// [`full_case_fold_str`]: fn.full_case_fold_str.html
+    pub fn full_case_fold_str(&self, s: &str) -> String {
+        convert_str(self, s, Case::FullFold, LengthPolicy::Unrestricted)
+    }
+
+    /// Convert a string to lowercase in place, as per [`lowercase_in_place`].
+    ///
+    /// [`lowercase_in_place`]: fn.lowercase_in_place.html
+    pub fn lowercase_in_place(&self, s: &mut String) {
+        convert_in_place(self, s, Case::Lower)
+    }
+
+    /// Convert a string to uppercase in place, as per [`uppercase_in_place`].
+    ///
+    /// [`uppercase_in_place`]: fn.uppercase_in_place.html
+    pub fn uppercase_in_place(&self, s: &mut String) {
+        convert_in_place(self, s, Case::Upper)
+    }
+
+    /// Convert a string slice to lowercase in place, without changing its length, as per
+    /// [`lowercase_str_in_place`].
+    ///
+    /// [`lowercase_str_in_place`]: fn.lowercase_str_in_place.html
+    pub fn lowercase_str_in_place(&self, s: &mut str) {
+        convert_str_in_place(self, s, Case::Lower)
+    }
+
+    /// Convert a string slice to uppercase in place, without changing its length, as per
+    /// [`uppercase_str_in_place`].
+    ///
+    /// [`uppercase_str_in_place`]: fn.uppercase_str_in_place.html
+    pub fn uppercase_str_in_place(&self, s: &mut str) {
+        convert_str_in_place(self, s, Case::Upper)
+    }
+
+    /// Convert a string to lowercase, also returning a map between offsets in `s` and the result,
+    /// as per [`lowercase_with_offsets`].
+    ///
+    /// [`lowercase_with_offsets`]: fn.lowercase_with_offsets.html
+    pub fn lowercase_with_offsets(&self, s: &str) -> (String, OffsetMap) {
+        convert_with_offsets(self, s, Case::Lower)
+    }
+
+    /// Convert a string to uppercase, also returning a map between offsets in `s` and the result,
+    /// as per [`uppercase_with_offsets`].
+    ///
+    /// [`uppercase_with_offsets`]: fn.uppercase_with_offsets.html
+    pub fn uppercase_with_offsets(&self, s: &str) -> (String, OffsetMap) {
+        convert_with_offsets(self, s, Case::Upper)
+    }
+
+    /// Convert possibly invalid UTF-8 to lowercase, appending the result to `out`, as per
+    /// [`lowercase_bytes`].
+    ///
+    /// [`lowercase_bytes`]: fn.lowercase_bytes.html
+    pub fn lowercase_bytes(&self, input: &[u8], mode: InvalidUtf8, out: &mut Vec<u8>) {
+        convert_bytes(self, input, mode, out, Case::Lower)
+    }
+
+    /// Convert possibly invalid UTF-8 to uppercase, appending the result to `out`, as per
+    /// [`uppercase_bytes`].
+    ///
+    /// [`uppercase_bytes`]: fn.uppercase_bytes.html
+    pub fn uppercase_bytes(&self, input: &[u8], mode: InvalidUtf8, out: &mut Vec<u8>) {
+        convert_bytes(self, input, mode, out, Case::Upper)
+    }
+
+    /// Case fold possibly invalid UTF-8, appending the result to `out`, as per
+    /// [`case_fold_bytes`].
+    ///
+    /// [`case_fold_bytes`]: fn.case_fold_bytes.html
+    pub fn case_fold_bytes(&self, input: &[u8], mode: InvalidUtf8, out: &mut Vec<u8>) {
+        convert_bytes(self, input, mode, out, Case::Fold)
+    }
+
+    /// Convert UTF-16 text to lowercase, appending the result to `out`, as per
+    /// [`lowercase_utf16`].
+    ///
+    /// [`lowercase_utf16`]: fn.lowercase_utf16.html
+    pub fn lowercase_utf16(&self, input: &[u16], out: &mut Vec<u16>) {
+        convert_utf16(self, input, out, Case::Lower)
+    }
+
+    /// Convert UTF-16 text to uppercase, appending the result to `out`, as per
+    /// [`uppercase_utf16`].
+    ///
+    /// [`uppercase_utf16`]: fn.uppercase_utf16.html
+    pub fn uppercase_utf16(&self, input: &[u16], out: &mut Vec<u16>) {
+        convert_utf16(self, input, out, Case::Upper)
+    }
+
+    /// Compare two strings for equality, ignoring case, as per [`caseless_eq`].
+    ///
+    /// [`caseless_eq`]: fn.caseless_eq.html
+    pub fn caseless_eq(&self, a: &str, b: &str) -> bool {
+        folded_eq(self, a, b)
+    }
+
+    /// Find the first occurrence of `needle` in `haystack`, ignoring case, as per
+    /// [`caseless_find`].
+    ///
+    /// [`caseless_find`]: fn.caseless_find.html
+    pub fn caseless_find(&self, haystack: &str, needle: &str) -> Option<Range<usize>> {
+        find_folded(self, haystack, needle)
+    }
+
+    /// Returns true if `haystack` contains `needle`, ignoring case, as per [`caseless_contains`].
+    ///
+    /// [`caseless_contains`]: fn.caseless_contains.html
+    pub fn caseless_contains(&self, haystack: &str, needle: &str) -> bool {
+        find_folded(self, haystack, needle).is_some()
+    }
+
+    /// Returns true if `haystack` starts with `needle`, ignoring case, as per
+    /// [`caseless_starts_with`].
+    ///
+    /// [`caseless_starts_with`]: fn.caseless_starts_with.html
+    pub fn caseless_starts_with(&self, haystack: &str, needle: &str) -> bool {
+        starts_with_folded(self, haystack, needle)
+    }
+
+    /// Returns true if `haystack` ends with `needle`, ignoring case, as per
+    /// [`caseless_ends_with`].
+    ///
+    /// [`caseless_ends_with`]: fn.caseless_ends_with.html
+    pub fn caseless_ends_with(&self, haystack: &str, needle: &str) -> bool {
+        ends_with_folded(self, haystack, needle)
+    }
+
+    /// Generate a sort/index key from the full case folding of a string, as per [`fold_key`].
+    ///
+    /// The key records the Unicode version of this data rather than [`UNICODE_VERSION`].
+    ///
+    /// [`fold_key`]: fn.fold_key.html
+    /// [`UNICODE_VERSION`]: constant.UNICODE_VERSION.html
+    pub fn fold_key(&self, s: &str) -> Box<[u8]> {
+        make_fold_key(self, self.unicode_version(), s)
+    }
+
+    /// Returns true if every lookup is in bounds and every mapping is a valid character, so that
+    /// the methods can't panic and always return valid mappings.
+    fn is_valid(&self) -> bool {
+        let valid_cp = |cp: u32| char::from_u32(cp).is_some();
+        let valid_mapping = |mapping: &[u32]| mapping.iter().all(|&cp| cp == 0 || valid_cp(cp));
+        let block_size = match 1usize.checked_shl(self.shift) {
+            Some(size) if self.shift < 16 => size,
+            _ => return false,
+        };
+        valid_cp(self.last_codepoint)
+            && self.records.first() == Some(&NO_MAPPING)
+            && self.records.iter().all(|(lower, upper, title)| {
+                valid_mapping(lower) && valid_mapping(upper) && valid_mapping(title)
+            })
+            && self.simple_mappings.windows(2).all(|w| w[0].0 < w[1].0)
+            && self
+                .simple_mappings
+                .iter()
+                .all(|&(cp, simple)| valid_cp(cp) && simple.iter().all(|&cp| valid_cp(cp)))
+            && self.case_folding.windows(2).all(|w| w[0].0 < w[1].0)
+            && self
+                .case_folding
+                .iter()
+                .all(|&(cp, folded)| valid_cp(cp) && valid_cp(folded) && folded != 0)
+            && self.blocks.len() % block_size == 0
+            && self
+                .blocks
+                .iter()
+                .all(|&index| usize::from(index) < self.records.len())
+            && self.block_offsets.len() > (self.last_codepoint >> self.shift) as usize
+            && self
+                .block_offsets
+                .iter()
+                .all(|&offset| usize::from(offset) + block_size <= self.blocks.len())
+    }
+}
+
+impl Tables for CaseData {
+    fn lookup(&self, u: u32) -> &Row {
+        if u <= self.last_codepoint {
+            let mask = (1 << self.shift) - 1;
+            let index = self.blocks
+                [usize::from(self.block_offsets[(u >> self.shift) as usize]) + (u & mask) as usize];
+            &self.records[usize::from(index)]
+        } else {
+            &NO_MAPPING
+        }
+    }
+
+    fn case_folded_u32(&self, cp: u32) -> Option<NonZeroU32> {
+        CaseData::case_folded_u32(self, cp)
+    }
+
+    fn simple_mappings(&self) -> &[(u32, [u32; 3])] {
+        &self.simple_mappings
+    }
+
+    fn chars_folding_to(&self, chr: char) -> &[char] {
+        CaseData::chars_folding_to(self, chr)
+    }
+}
+
+/// Group the characters that fold to each folded character, including the folded character
+/// itself, as in the inverse table generated by the build script. `case_folding` must only contain
+/// valid code points.
+fn invert(case_folding: &[(u32, u32)]) -> Vec<(u32, Vec<char>)> {
+    let mut pairs: Vec<(u32, u32)> = case_folding
+        .iter()
+        .map(|&(cp, folded)| (folded, cp))
+        .collect();
+    pairs.sort_unstable();
+    let to_char = |cp| char::from_u32(cp).expect("invalid code point in case folding");
+    let mut inverse: Vec<(u32, Vec<char>)> = Vec::new();
+    for (folded, cp) in pairs {
+        match inverse.last_mut() {
+            Some((last, chars)) if *last == folded => chars.push(to_char(cp)),
+            _ => inverse.push((folded, vec![to_char(folded), to_char(cp)])),
+        }
+    }
+    for (_, chars) in &mut inverse {
+        chars.sort_unstable();
+    }
+    inverse
+}
+
+/// Reads little endian integers from a slice that is known to be long enough.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> &'a [u8] {
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        bytes
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.take(1)[0]
+    }
+
+    fn u16(&mut self) -> u16 {
+        let bytes = self.take(2);
+        u16::from_le_bytes([bytes[0], bytes[1]])
+    }
+
+    fn u32(&mut self) -> u32 {
+        let bytes = self.take(4);
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+}
+
+/// The CRC-32 used by zlib and PNG.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        *entry = (0..8).fold(n as u32, |c, _| {
+            if c & 1 != 0 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            }
+        });
+    }
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        table[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
@@ -1,5 +1,7 @@
+use std::char;
 use std::num::NonZeroU32;
 
+use crate::case_closure;
 use crate::case_folding_simple;
 
 include!(concat!(env!("OUT_DIR"), "/case_mapping.rs")); // generated by build.rs
@@ -7,6 +9,48 @@ include!(concat!(env!("OUT_DIR"), "/case_mapping.rs")); // generated by build.rs
 const MASK: usize = BLOCK_SIZE - 1;
 const SHIFT: usize = MASK.count_ones() as usize;
 
+/// A source of case mapping tables: the tables compiled into the crate, or a `CaseData`.
+///
+/// The string-level conversions are generic over this so that they can be used with either.
+pub(crate) trait Tables {
+    /// Returns the record of lowercase, uppercase, and titlecase mappings of a code point.
+    fn lookup(&self, cp: u32) -> &Row;
+
+    /// Returns the simple case folding of a code point.
+    fn case_folded_u32(&self, cp: u32) -> Option<NonZeroU32>;
+
+    /// The simple mappings of characters that have a full mapping of more than one code point,
+    /// sorted by code point.
+    fn simple_mappings(&self) -> &[(u32, [u32; 3])];
+
+    /// Returns the sorted characters that case fold to `chr`, as per `chars_folding_to`.
+    fn chars_folding_to(&self, chr: char) -> &[char];
+}
+
+/// The tables compiled into the crate, which are used by the free functions.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Builtin;
+
+impl Tables for Builtin {
+    #[inline]
+    fn lookup(&self, cp: u32) -> &Row {
+        lookup(cp)
+    }
+
+    #[inline]
+    fn case_folded_u32(&self, cp: u32) -> Option<NonZeroU32> {
+        case_folding_simple::case_folding_simple(cp)
+    }
+
+    fn simple_mappings(&self) -> &[(u32, [u32; 3])] {
+        &SIMPLE_CASE_MAPPINGS
+    }
+
+    fn chars_folding_to(&self, chr: char) -> &[char] {
+        case_closure::chars_folding_to(chr)
+    }
+}
+
 /// Map the supplied character to its lowercase equivalent.
 ///
 /// The lowercase equivalent may be more than one code point. Unused elements in the returned
@@ -42,6 +86,193 @@ pub fn case_folded(chr: char) -> Option<NonZeroU32> {
     case_folding_simple::case_folding_simple(chr as u32)
 }
 
+/// Map the supplied character to its simple lowercase equivalent.
+///
+/// This is the Simple_Lowercase_Mapping property from UnicodeData.txt, which is always a single
+/// character. It differs from [`to_lowercase`] only for characters whose full mapping is more
+/// than one code point.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::simple_lowercase('İ'), 'i');
+/// assert_eq!(unicode_case_mapping::simple_lowercase('A'), 'a');
+/// ```
+///
+/// [`to_lowercase`]: fn.to_lowercase.html
+pub fn simple_lowercase(chr: char) -> char {
+    simple_mapping(&Builtin, chr, 0)
+}
+
+/// Map the supplied character to its simple uppercase equivalent.
+///
+/// This is the Simple_Uppercase_Mapping property from UnicodeData.txt, which is always a single
+/// character. It differs from [`to_uppercase`] only for characters whose full mapping is more
+/// than one code point.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::simple_uppercase('ß'), 'ß');
+/// assert_eq!(unicode_case_mapping::simple_uppercase('ᾳ'), 'ᾼ');
+/// assert_eq!(unicode_case_mapping::simple_uppercase('a'), 'A');
+/// ```
+///
+/// [`to_uppercase`]: fn.to_uppercase.html
+pub fn simple_uppercase(chr: char) -> char {
+    simple_mapping(&Builtin, chr, 1)
+}
+
+/// Map the supplied character to its simple titlecase equivalent.
+///
+/// This is the Simple_Titlecase_Mapping property from UnicodeData.txt, which is always a single
+/// character. It differs from [`to_titlecase`] only for characters whose full mapping is more
+/// than one code point.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::simple_titlecase('ß'), 'ß');
+/// assert_eq!(unicode_case_mapping::simple_titlecase('ǆ'), 'ǅ');
+/// ```
+///
+/// [`to_titlecase`]: fn.to_titlecase.html
+pub fn simple_titlecase(chr: char) -> char {
+    simple_mapping(&Builtin, chr, 2)
+}
+
+/// Returns the simple lowercase, uppercase, or titlecase mapping of `chr`, selected by `index`.
+pub(crate) fn simple_mapping<T: Tables>(tables: &T, chr: char, index: usize) -> char {
+    let (lower, upper, title) = tables.lookup(chr as u32);
+    let full: &[u32] = match index {
+        0 => lower,
+        1 => upper,
+        _ => title,
+    };
+    if full[0] == 0 {
+        return chr;
+    }
+    let cp = if full[1] == 0 {
+        full[0]
+    } else {
+        let u = chr as u32;
+        let simple_mappings = tables.simple_mappings();
+        simple_mappings
+            .binary_search_by(|&(cp, _)| cp.cmp(&u))
+            .map(|i| simple_mappings[i].1[index])
+            .unwrap_or(u)
+    };
+    char::from_u32(cp).unwrap_or(chr)
+}
+
+/// Map the supplied character to the opposite case.
+///
+/// Uppercase characters are mapped to their lowercase equivalent and lowercase characters to
+/// their uppercase equivalent, which may be more than one code point. Titlecase characters, such
+/// as the digraph `ǅ`, are mapped to lowercase. Unused elements in the returned array are set to 0.
+///
+/// **Note:** A result of all zeros indicates the codepoint maps to itself.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::toggle_case;
+///
+/// assert_eq!(toggle_case('a'), ['A' as u32, 0, 0]);
+/// assert_eq!(toggle_case('Ǆ'), ['ǆ' as u32, 0, 0]);
+/// assert_eq!(toggle_case('ǅ'), ['ǆ' as u32, 0, 0]);
+/// assert_eq!(toggle_case('ß'), ['S' as u32, 'S' as u32, 0]);
+/// assert_eq!(toggle_case('1'), [0; 3]);
+/// ```
+pub fn toggle_case(chr: char) -> [u32; 3] {
+    toggle(lookup(chr as u32))
+}
+
+/// Returns the opposite case mapping from the record of a character.
+pub(crate) fn toggle((lower, upper, _): &Row) -> [u32; 3] {
+    // Characters with a lowercase mapping are uppercase or titlecase. Otherwise the character is
+    // lowercase if it has an uppercase mapping, or has no case.
+    if lower[0] != 0 {
+        [lower[0], lower[1], 0]
+    } else {
+        *upper
+    }
+}
+
+/// Map the supplied code point to its lowercase equivalent.
+///
+/// This is the same as [`to_lowercase`] but accepts any `u32`, such as a code point read from a
+/// font. Surrogate code points and values above U+10FFFF have no mapping and so map to
+/// themselves, resulting in all zeros.
+///
+/// [`to_lowercase`]: fn.to_lowercase.html
+pub fn to_lowercase_u32(cp: u32) -> [u32; 2] {
+    lookup(cp).0
+}
+
+/// Map the supplied code point to its uppercase equivalent.
+///
+/// This is the same as [`to_uppercase`] but accepts any `u32`, such as a code point read from a
+/// font. Surrogate code points and values above U+10FFFF have no mapping and so map to
+/// themselves, resulting in all zeros.
+///
+/// [`to_uppercase`]: fn.to_uppercase.html
+pub fn to_uppercase_u32(cp: u32) -> [u32; 3] {
+    lookup(cp).1
+}
+
+/// Map the supplied code point to its titlecase equivalent.
+///
+/// This is the same as [`to_titlecase`] but accepts any `u32`, such as a code point read from a
+/// font. Surrogate code points and values above U+10FFFF have no mapping and so map to
+/// themselves, resulting in all zeros.
+///
+/// [`to_titlecase`]: fn.to_titlecase.html
+pub fn to_titlecase_u32(cp: u32) -> [u32; 3] {
+    lookup(cp).2
+}
+
+/// Map the supplied code point to its case-folded equivalent.
+///
+/// This is the same as [`case_folded`] but accepts any `u32`. Surrogate code points and values
+/// above U+10FFFF have no case folding and so return `None`.
+///
+/// [`case_folded`]: fn.case_folded.html
+pub fn case_folded_u32(cp: u32) -> Option<NonZeroU32> {
+    case_folding_simple::case_folding_simple(cp)
+}
+
+/// Map the supplied character to its full case folding.
+///
+/// Full case folding (statuses C and F in CaseFolding.txt) differs from simple case folding for
+/// characters that fold to more than one code point, such as `ß` → `ss`. It is derived from the
+/// simple case folding and the full uppercase and lowercase mappings: a character whose
+/// uppercase mapping expands folds to the simple case folding of each character in that
+/// expansion.
+///
+/// Unlike the public mapping functions, a character that maps to itself is returned as itself
+/// rather than all zeros.
+pub(crate) fn full_case_folding<T: Tables>(tables: &T, chr: char) -> [u32; 3] {
+    let folded = tables
+        .case_folded_u32(chr as u32)
+        .map_or(chr as u32, NonZeroU32::get);
+    let (lower, upper, _) = tables.lookup(folded);
+    if upper[1] != 0 {
+        let mut mapped = [0; 3];
+        for (dest, &cp) in mapped.iter_mut().zip(upper.iter()) {
+            if cp != 0 {
+                *dest = tables.case_folded_u32(cp).map_or(cp, NonZeroU32::get);
+            }
+        }
+        mapped
+    } else if lower[1] != 0 {
+        [lower[0], lower[1], 0]
+    } else {
+        [folded, 0, 0]
+    }
+}
+
+#[inline]
 fn lookup(u: u32) -> &'static Row {
     if u <= LAST_CODEPOINT {
         let index = CASE_MAPPING_BLOCKS
@@ -0,0 +1,250 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::str::Chars;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::case_mapping::{Builtin, Tables};
+use crate::string::{mapped_chars, Case, MapChars};
+
+/// Compare two strings for equality, ignoring case.
+///
+/// Strings are compared by their full case folding, so `ß` is equal to `ss` and `ẞ`. The
+/// folding is computed as the strings are compared rather than allocating folded copies.
+///
+/// ### Example
+///
+/// ```
+/// assert!(unicode_case_mapping::caseless_eq("Straße", "STRASSE"));
+/// assert!(unicode_case_mapping::caseless_eq("ΣΊΣΥΦΟΣ", "σίσυφοσ"));
+/// assert!(!unicode_case_mapping::caseless_eq("Straße", "Strasse!"));
+/// ```
+pub fn caseless_eq(a: &str, b: &str) -> bool {
+    folded_eq(&Builtin, a, b)
+}
+
+/// Compare `a` and `b` as per `caseless_eq`, using `tables`.
+pub(crate) fn folded_eq<T: Tables>(tables: &T, a: &str, b: &str) -> bool {
+    (a.len() == b.len() && a.eq_ignore_ascii_case(b)) || folded(tables, a).eq(folded(tables, b))
+}
+
+/// Find the first occurrence of `needle` in `haystack`, ignoring case.
+///
+/// Strings are compared by their full case folding. The returned range is the byte range of the
+/// match in the original `haystack`, which may differ in length from `needle` when folding
+/// changes the length of the text, for example `ß` in the haystack matching `ss` in the needle.
+/// Matches must begin and end on character boundaries of `haystack`, so `s` does not match part
+/// of `ß`.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::caseless_find;
+///
+/// assert_eq!(caseless_find("Die Straße", "STRASSE"), Some(4..11));
+/// assert_eq!(caseless_find("Die Straße", "SSE"), Some(8..11));
+/// assert_eq!(caseless_find("Die Straße", "se"), None);
+/// assert_eq!(caseless_find("ΣΊΣΥΦΟΣ", "υφο"), Some(6..12));
+/// // U+0000 is matched like any other character
+/// assert_eq!(caseless_find("a\0b", "ab"), None);
+/// ```
+pub fn caseless_find(haystack: &str, needle: &str) -> Option<Range<usize>> {
+    find_folded(&Builtin, haystack, needle)
+}
+
+/// Find `needle` in `haystack` as per `caseless_find`, using `tables`.
+pub(crate) fn find_folded<T: Tables>(
+    tables: &T,
+    haystack: &str,
+    needle: &str,
+) -> Option<Range<usize>> {
+    let needle: Vec<char> = folded(tables, needle).collect();
+    haystack
+        .char_indices()
+        .map(|(start, _)| start)
+        .chain(Some(haystack.len()))
+        .filter_map(|start| match_at(tables, haystack, start, &needle).map(|end| start..end))
+        .next()
+}
+
+/// Returns true if `haystack` contains `needle`, ignoring case.
+///
+/// See [`caseless_find`] for details of how strings are matched.
+///
+/// ### Example
+///
+/// ```
+/// assert!(unicode_case_mapping::caseless_contains("Die Straße", "STRASSE"));
+/// ```
+///
+/// [`caseless_find`]: fn.caseless_find.html
+pub fn caseless_contains(haystack: &str, needle: &str) -> bool {
+    caseless_find(haystack, needle).is_some()
+}
+
+/// Returns true if `haystack` starts with `needle`, ignoring case.
+///
+/// See [`caseless_find`] for details of how strings are matched.
+///
+/// ### Example
+///
+/// ```
+/// assert!(unicode_case_mapping::caseless_starts_with("Straße", "STRASS"));
+/// assert!(!unicode_case_mapping::caseless_starts_with("Straße", "STRAS"));
+/// ```
+///
+/// [`caseless_find`]: fn.caseless_find.html
+pub fn caseless_starts_with(haystack: &str, needle: &str) -> bool {
+    starts_with_folded(&Builtin, haystack, needle)
+}
+
+pub(crate) fn starts_with_folded<T: Tables>(tables: &T, haystack: &str, needle: &str) -> bool {
+    let needle: Vec<char> = folded(tables, needle).collect();
+    match_at(tables, haystack, 0, &needle).is_some()
+}
+
+/// Returns true if `haystack` ends with `needle`, ignoring case.
+///
+/// See [`caseless_find`] for details of how strings are matched.
+///
+/// ### Example
+///
+/// ```
+/// assert!(unicode_case_mapping::caseless_ends_with("Straße", "SSE"));
+/// assert!(!unicode_case_mapping::caseless_ends_with("Straße", "SE"));
+/// assert!(!unicode_case_mapping::caseless_ends_with("a\0", "a"));
+/// ```
+///
+/// [`caseless_find`]: fn.caseless_find.html
+pub fn caseless_ends_with(haystack: &str, needle: &str) -> bool {
+    ends_with_folded(&Builtin, haystack, needle)
+}
+
+pub(crate) fn ends_with_folded<T: Tables>(tables: &T, haystack: &str, needle: &str) -> bool {
+    let needle: Vec<char> = folded(tables, needle).collect();
+    let mut remaining = needle.len();
+    for chr in haystack.chars().rev() {
+        if remaining == 0 {
+            break;
+        }
+        let mapped = Case::FullFold.map(tables, chr);
+        let len = mapped_chars(&mapped).count();
+        for &cp in mapped[..len].iter().rev() {
+            // Fail if the needle is exhausted part way through the expansion of `chr`
+            if remaining == 0 || cp != needle[remaining - 1] as u32 {
+                return false;
+            }
+            remaining -= 1;
+        }
+    }
+    remaining == 0
+}
+
+/// Match the folded `needle` against `haystack` starting at byte offset `start`.
+///
+/// Returns the byte offset of the end of the match. The match must end on a character boundary.
+fn match_at<T: Tables>(tables: &T, haystack: &str, start: usize, needle: &[char]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(start);
+    }
+    let mut matched = 0;
+    for (offset, chr) in haystack[start..].char_indices() {
+        for folded in mapped_chars(&Case::FullFold.map(tables, chr)) {
+            if matched == needle.len() || folded != needle[matched] {
+                return None;
+            }
+            matched += 1;
+        }
+        if matched == needle.len() {
+            return Some(start + offset + chr.len_utf8());
+        }
+    }
+    None
+}
+
+/// A string wrapper that ignores case when compared or hashed.
+///
+/// The `PartialEq`, `Eq`, `PartialOrd`, `Ord`, and `Hash` implementations use the full case
+/// folding of the wrapped string, which is computed on the fly. This allows `Caseless` to be used
+/// as a `HashMap` or `BTreeMap` key without storing a folded copy of the string. Strings are
+/// ordered by the code points of their case folding.
+///
+/// ### Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use unicode_case_mapping::Caseless;
+///
+/// let mut map = HashMap::new();
+/// map.insert(Caseless("Maße"), 1);
+/// assert_eq!(map.get(&Caseless("MASSE")), Some(&1));
+/// assert!(Caseless("apple") < Caseless("BANANA"));
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Caseless<S>(pub S);
+
+impl<S: AsRef<str>> Caseless<S> {
+    /// Wrap a string.
+    pub fn new(s: S) -> Self {
+        Caseless(s)
+    }
+
+    /// Returns the wrapped string.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+
+    fn folded(&self) -> MapChars<'_, Chars<'_>> {
+        folded(&Builtin, self.0.as_ref())
+    }
+}
+
+impl<S: AsRef<str>> AsRef<str> for Caseless<S> {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S: AsRef<str>> fmt::Display for Caseless<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.as_ref())
+    }
+}
+
+impl<S: AsRef<str>, T: AsRef<str>> PartialEq<Caseless<T>> for Caseless<S> {
+    fn eq(&self, other: &Caseless<T>) -> bool {
+        caseless_eq(self.0.as_ref(), other.0.as_ref())
+    }
+}
+
+impl<S: AsRef<str>> Eq for Caseless<S> {}
+
+impl<S: AsRef<str>, T: AsRef<str>> PartialOrd<Caseless<T>> for Caseless<S> {
+    fn partial_cmp(&self, other: &Caseless<T>) -> Option<Ordering> {
+        Some(self.folded().cmp(other.folded()))
+    }
+}
+
+impl<S: AsRef<str>> Ord for Caseless<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl<S: AsRef<str>> Hash for Caseless<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for chr in self.folded() {
+            state.write_u32(chr as u32);
+        }
+        // Terminate the string so that adjacent keys in a tuple hash differently, as `str` does
+        state.write_u8(0xFF);
+    }
+}
+
+fn folded<'a, T: Tables>(tables: &'a T, s: &'a str) -> MapChars<'a, Chars<'a>, T> {
+    MapChars::new(tables, s.chars(), Case::FullFold)
+}
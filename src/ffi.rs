@@ -0,0 +1,196 @@
+//! C ABI for use from C and other languages, enabled with the `ffi` feature.
+//!
+//! The header `include/unicode_case_mapping.h` is generated from this module with cbindgen (run
+//! `make header`). To link C code against the same tables as Rust code, build a static or shared
+//! library with `cargo rustc --release --features ffi --crate-type staticlib` (or `cdylib`).
+//!
+//! The code point functions follow the Rust functions of the same name: the mapping is written
+//! to `out` with unused elements set to 0, and all zeros means the code point maps to itself. The
+//! UTF-8 functions use the same convention as `snprintf`: they return the length of the
+//! converted text and only write it if it fits in the supplied buffer.
+
+use std::slice;
+
+use crate::{
+    case_fold_bytes, case_folded_u32, lowercase_bytes, to_lowercase_u32, to_titlecase_u32,
+    to_uppercase_u32, uppercase_bytes, InvalidUtf8, UNICODE_VERSION,
+};
+
+/// Copy invalid UTF-8 sequences to the output unchanged.
+pub const UCM_INVALID_UTF8_PASS_THROUGH: u32 = 0;
+
+/// Replace each invalid UTF-8 sequence with U+FFFD REPLACEMENT CHARACTER.
+pub const UCM_INVALID_UTF8_REPLACE: u32 = 1;
+
+/// Write the version of Unicode the tables were generated from to `out` as major, minor, and
+/// update.
+///
+/// # Safety
+///
+/// `out` must point to an array of 3 `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn ucm_unicode_version(out: *mut u32) {
+    let (major, minor, update) = UNICODE_VERSION;
+    write_mapping(out, [major as u32, minor as u32, update as u32]);
+}
+
+/// Map a code point to its lowercase equivalent, returning the number of code points written to
+/// `out`.
+///
+/// Returns 0, with `out` set to all zeros, if the code point maps to itself. This includes
+/// surrogates and values above U+10FFFF.
+///
+/// # Safety
+///
+/// `out` must point to an array of 3 `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn ucm_to_lowercase(cp: u32, out: *mut u32) -> usize {
+    let [a, b] = to_lowercase_u32(cp);
+    write_mapping(out, [a, b, 0])
+}
+
+/// Map a code point to its uppercase equivalent, returning the number of code points written to
+/// `out`.
+///
+/// Returns 0, with `out` set to all zeros, if the code point maps to itself. This includes
+/// surrogates and values above U+10FFFF.
+///
+/// # Safety
+///
+/// `out` must point to an array of 3 `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn ucm_to_uppercase(cp: u32, out: *mut u32) -> usize {
+    write_mapping(out, to_uppercase_u32(cp))
+}
+
+/// Map a code point to its titlecase equivalent, returning the number of code points written to
+/// `out`.
+///
+/// Returns 0, with `out` set to all zeros, if the code point maps to itself. This includes
+/// surrogates and values above U+10FFFF.
+///
+/// # Safety
+///
+/// `out` must point to an array of 3 `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn ucm_to_titlecase(cp: u32, out: *mut u32) -> usize {
+    write_mapping(out, to_titlecase_u32(cp))
+}
+
+/// Returns the simple case folding of a code point, or 0 if it folds to itself.
+#[no_mangle]
+pub extern "C" fn ucm_case_folded(cp: u32) -> u32 {
+    case_folded_u32(cp).map_or(0, |folded| folded.get())
+}
+
+/// Convert UTF-8 text to lowercase.
+///
+/// Returns the length in bytes of the converted text. The text is written to `out` only if
+/// `out_len` is at least this length, otherwise `out` is left untouched and the caller can
+/// retry with a larger buffer. The output is not NUL terminated. `invalid_utf8` is one of the
+/// `UCM_INVALID_UTF8_*` constants, other values are treated as `UCM_INVALID_UTF8_PASS_THROUGH`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes and `out` to `out_len` writable bytes. Either
+/// may be null if the corresponding length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn ucm_lowercase_utf8(
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: usize,
+    invalid_utf8: u32,
+) -> usize {
+    convert_utf8(
+        input,
+        input_len,
+        out,
+        out_len,
+        invalid_utf8,
+        lowercase_bytes,
+    )
+}
+
+/// Convert UTF-8 text to uppercase.
+///
+/// The arguments and return value are as per `ucm_lowercase_utf8`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes and `out` to `out_len` writable bytes. Either
+/// may be null if the corresponding length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn ucm_uppercase_utf8(
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: usize,
+    invalid_utf8: u32,
+) -> usize {
+    convert_utf8(
+        input,
+        input_len,
+        out,
+        out_len,
+        invalid_utf8,
+        uppercase_bytes,
+    )
+}
+
+/// Apply simple case folding to UTF-8 text.
+///
+/// The arguments and return value are as per `ucm_lowercase_utf8`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes and `out` to `out_len` writable bytes. Either
+/// may be null if the corresponding length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn ucm_case_fold_utf8(
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: usize,
+    invalid_utf8: u32,
+) -> usize {
+    convert_utf8(
+        input,
+        input_len,
+        out,
+        out_len,
+        invalid_utf8,
+        case_fold_bytes,
+    )
+}
+
+unsafe fn write_mapping(out: *mut u32, mapping: [u32; 3]) -> usize {
+    slice::from_raw_parts_mut(out, mapping.len()).copy_from_slice(&mapping);
+    mapping.iter().take_while(|&&cp| cp != 0).count()
+}
+
+unsafe fn convert_utf8(
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: usize,
+    invalid_utf8: u32,
+    convert: fn(&[u8], InvalidUtf8, &mut Vec<u8>),
+) -> usize {
+    let input = if input_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let mode = if invalid_utf8 == UCM_INVALID_UTF8_REPLACE {
+        InvalidUtf8::Replace
+    } else {
+        InvalidUtf8::PassThrough
+    };
+    let mut converted = Vec::with_capacity(input.len());
+    convert(input, mode, &mut converted);
+    if converted.len() <= out_len && !converted.is_empty() {
+        slice::from_raw_parts_mut(out, converted.len()).copy_from_slice(&converted);
+    }
+    converted.len()
+}
@@ -0,0 +1,295 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter;
+use std::ops::Deref;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::case_mapping::{Builtin, Tables};
+use crate::string::{convert_str_into, full_case_fold_str, mapped_chars, Case, LengthPolicy};
+use crate::UNICODE_VERSION;
+
+/// The first byte of every fold key. It never occurs in UTF-8.
+const HEADER_TAG: u8 = 0xFF;
+
+/// The version of the fold key layout, incremented if the layout of the key itself changes.
+const FORMAT_VERSION: u8 = 1;
+
+const HEADER_LEN: usize = 5;
+
+/// Generate a sort/index key from the full case folding of a string.
+///
+/// Keys for strings that are equal ignoring case (as per [`caseless_eq`]) are identical, and
+/// keys can be compared with plain byte comparison. The key begins with a short header that
+/// records the Unicode version it was built with, followed by the UTF-8 encoded full case
+/// folding of `s`. Use [`fold_key_unicode_version`] to detect keys built with different data,
+/// which should be rebuilt.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::fold_key;
+///
+/// assert_eq!(fold_key("Straße"), fold_key("STRASSE"));
+/// assert!(fold_key("apple") < fold_key("BANANA"));
+/// ```
+///
+/// [`caseless_eq`]: fn.caseless_eq.html
+/// [`fold_key_unicode_version`]: fn.fold_key_unicode_version.html
+pub fn fold_key(s: &str) -> Box<[u8]> {
+    make_fold_key(&Builtin, UNICODE_VERSION, s)
+}
+
+/// Generate a fold key for `s` as per `fold_key`, using `tables` generated from `unicode_version`.
+pub(crate) fn make_fold_key<T: Tables>(
+    tables: &T,
+    unicode_version: (u64, u64, u64),
+    s: &str,
+) -> Box<[u8]> {
+    let mut key = Vec::with_capacity(HEADER_LEN + s.len());
+    key.extend_from_slice(&header(unicode_version));
+    convert_str_into(
+        tables,
+        s,
+        &mut key,
+        Case::FullFold,
+        LengthPolicy::Unrestricted,
+    );
+    key.into_boxed_slice()
+}
+
+fn header(unicode_version: (u64, u64, u64)) -> [u8; HEADER_LEN] {
+    let (major, minor, update) = unicode_version;
+    [
+        HEADER_TAG,
+        FORMAT_VERSION,
+        major as u8,
+        minor as u8,
+        update as u8,
+    ]
+}
+
+/// Returns the Unicode version a key generated by [`fold_key`] or [`FoldKeyWriter`] was built
+/// with.
+///
+/// Returns `None` if `key` is not a fold key, or was produced by an incompatible version of this
+/// crate. Keys whose version differs from [`UNICODE_VERSION`] should be rebuilt.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::{fold_key, fold_key_unicode_version, UNICODE_VERSION};
+///
+/// assert_eq!(fold_key_unicode_version(&fold_key("term")), Some(UNICODE_VERSION));
+/// assert_eq!(fold_key_unicode_version(b"term"), None);
+/// ```
+///
+/// [`fold_key`]: fn.fold_key.html
+/// [`FoldKeyWriter`]: struct.FoldKeyWriter.html
+/// [`UNICODE_VERSION`]: constant.UNICODE_VERSION.html
+pub fn fold_key_unicode_version(key: &[u8]) -> Option<(u64, u64, u64)> {
+    if key.len() >= HEADER_LEN && key[0] == HEADER_TAG && key[1] == FORMAT_VERSION {
+        Some((u64::from(key[2]), u64::from(key[3]), u64::from(key[4])))
+    } else {
+        None
+    }
+}
+
+/// Incrementally write a fold key.
+///
+/// Writing a string in several pieces produces the same key as passing the concatenation of the
+/// pieces to [`fold_key`].
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::{fold_key, FoldKeyWriter};
+///
+/// let mut writer = FoldKeyWriter::new(Vec::new());
+/// writer.write_str("GROẞ")?;
+/// writer.write_str("STADT")?;
+/// assert_eq!(writer.into_inner()?, &*fold_key("Großstadt"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [`fold_key`]: fn.fold_key.html
+#[derive(Debug)]
+pub struct FoldKeyWriter<W: Write> {
+    inner: W,
+    header_written: bool,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> FoldKeyWriter<W> {
+    /// Create a writer that writes a fold key to `inner`.
+    pub fn new(inner: W) -> Self {
+        FoldKeyWriter {
+            inner,
+            header_written: false,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Append the case folding of `s` to the key.
+    pub fn write_str(&mut self, s: &str) -> io::Result<()> {
+        self.write_header()?;
+        self.buf.clear();
+        convert_str_into(
+            &Builtin,
+            s,
+            &mut self.buf,
+            Case::FullFold,
+            LengthPolicy::Unrestricted,
+        );
+        self.inner.write_all(&self.buf)
+    }
+
+    /// Finish the key, returning the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.write_header()?;
+        Ok(self.inner)
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if !self.header_written {
+            self.inner.write_all(&header(UNICODE_VERSION))?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+}
+
+/// A string in full case folded form, as produced by [`full_case_fold_str`].
+///
+/// Two strings are equal ignoring case (as per [`caseless_eq`]) exactly when their
+/// `FoldedString`s are equal, so this is suitable as a search term or index key. With the `serde`
+/// feature it serializes as a plain string, and deserializing checks that the string is case
+/// folded so that a corrupt index is rejected rather than silently failing to match.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::FoldedString;
+///
+/// let folded = FoldedString::new("Straße");
+/// assert_eq!(folded.as_str(), "strasse");
+/// assert_eq!(folded, FoldedString::new("STRASSE"));
+///
+/// assert!(FoldedString::from_folded("strasse".to_string()).is_ok());
+/// let err = FoldedString::from_folded("straße".to_string()).unwrap_err();
+/// assert_eq!(err.valid_up_to(), 4);
+/// ```
+///
+/// [`full_case_fold_str`]: fn.full_case_fold_str.html
+/// [`caseless_eq`]: fn.caseless_eq.html
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FoldedString(String);
+
+impl FoldedString {
+    /// Case fold `s`.
+    pub fn new(s: &str) -> Self {
+        FoldedString(full_case_fold_str(s))
+    }
+
+    /// Wrap a string that is already case folded, without copying it.
+    ///
+    /// Returns an error containing the string if it is not in case folded form.
+    pub fn from_folded(s: String) -> Result<Self, NotFoldedError> {
+        let unfolded = s.char_indices().find(|&(_, chr)| {
+            !mapped_chars(&Case::FullFold.map(&Builtin, chr)).eq(iter::once(chr))
+        });
+        match unfolded {
+            Some((valid_up_to, _)) => Err(NotFoldedError {
+                string: s,
+                valid_up_to,
+            }),
+            None => Ok(FoldedString(s)),
+        }
+    }
+
+    /// Returns the folded string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the folded string, consuming `self`.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for FoldedString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for FoldedString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for FoldedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<FoldedString> for String {
+    fn from(folded: FoldedString) -> String {
+        folded.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for FoldedString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for FoldedString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        FoldedString::from_folded(s).map_err(de::Error::custom)
+    }
+}
+
+/// The error returned by [`FoldedString::from_folded`] when the string is not case folded.
+///
+/// [`FoldedString::from_folded`]: struct.FoldedString.html#method.from_folded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotFoldedError {
+    string: String,
+    valid_up_to: usize,
+}
+
+impl NotFoldedError {
+    /// The byte offset of the first character that changes when case folded.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Returns the string that was not case folded.
+    pub fn into_string(self) -> String {
+        self.string
+    }
+}
+
+impl fmt::Display for NotFoldedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string is not case folded: character at byte {} changes when folded",
+            self.valid_up_to
+        )
+    }
+}
+
+impl Error for NotFoldedError {}
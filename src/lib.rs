@@ -14,12 +14,55 @@
 //! assert_eq!(unicode_case_mapping::case_folded('I'), NonZeroU32::new('i' as u32));
 //! assert_eq!(unicode_case_mapping::case_folded('ß'), None);
 //! assert_eq!(unicode_case_mapping::case_folded('ẞ'), NonZeroU32::new('ß' as u32));
+//!
+//! // Raw code points, such as those from a font, can be mapped without converting to `char`
+//! assert_eq!(unicode_case_mapping::to_uppercase_u32(0x1E9E), [0; 3]);
+//! assert_eq!(unicode_case_mapping::to_lowercase_u32(0x1E9E), [0xDF, 0]);
+//! assert_eq!(unicode_case_mapping::to_lowercase_u32(0xD800), [0; 2]);
+//! assert_eq!(unicode_case_mapping::case_folded_u32(0x110000), None);
 //! ```
 
+mod ascii;
+mod bytes;
+mod case_closure;
+mod case_data;
 mod case_folding_simple;
 mod case_mapping;
+mod caseless;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fold_key;
+mod matcher;
+mod offsets;
+mod string;
+#[allow(clippy::redundant_static_lifetimes)]
 mod tables;
-pub use case_mapping::{case_folded, to_lowercase, to_titlecase, to_uppercase};
+mod utf16;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub use bytes::{case_fold_bytes, lowercase_bytes, uppercase_bytes, InvalidUtf8};
+pub use case_closure::{case_closure, case_fold_ranges, chars_folding_to, CaseClosure};
+pub use case_data::{CaseData, CaseDataError};
+pub use case_mapping::{
+    case_folded, case_folded_u32, simple_lowercase, simple_titlecase, simple_uppercase,
+    to_lowercase, to_lowercase_u32, to_titlecase, to_titlecase_u32, to_uppercase, to_uppercase_u32,
+    toggle_case,
+};
+pub use caseless::{
+    caseless_contains, caseless_ends_with, caseless_eq, caseless_find, caseless_starts_with,
+    Caseless,
+};
+pub use fold_key::{
+    fold_key, fold_key_unicode_version, FoldKeyWriter, FoldedString, NotFoldedError,
+};
+pub use matcher::{CaselessMatch, CaselessMatcher, CaselessMatches};
+pub use offsets::{lowercase_with_offsets, uppercase_with_offsets, OffsetMap, OffsetSegment};
+pub use string::{
+    full_case_fold_str, lowercase_in_place, lowercase_str, lowercase_str_in_place,
+    lowercase_str_with, swapcase_str, titlecase_str, uppercase_in_place, uppercase_str,
+    uppercase_str_in_place, uppercase_str_with, LengthPolicy, Titlecaser,
+};
+pub use utf16::{lowercase_utf16, uppercase_utf16};
 
 /// The version of [Unicode](http://www.unicode.org/)
 /// that this version of unicode-case-mapping was generated from.
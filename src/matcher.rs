@@ -0,0 +1,283 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::str::CharIndices;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::case_mapping::Builtin;
+use crate::string::{mapped_chars, Case, MapChars};
+
+/// Marks a position in the folded input that is not the first character of a folded character.
+const NOT_BOUNDARY: usize = std::usize::MAX;
+
+const ROOT: usize = 0;
+
+/// Matches many patterns against text at once, ignoring case.
+///
+/// The patterns are case folded once, with full case folding, and compiled into an
+/// Aho–Corasick automaton over the folded characters. Input text is folded as it is scanned and
+/// matches are reported as byte offsets into the original, unfolded text. As with
+/// [`caseless_find`], matches must begin and end on character boundaries of the input. Empty
+/// patterns never match.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::CaselessMatcher;
+///
+/// let matcher = CaselessMatcher::new(&["strasse", "SE", "dïe"]);
+/// let text = "DÏE Straße";
+/// let matches: Vec<_> = matcher
+///     .find_iter(text)
+///     .map(|m| (m.pattern(), &text[m.range()]))
+///     .collect();
+/// assert_eq!(matches, vec![(2, "DÏE"), (0, "Straße")]);
+/// ```
+///
+/// [`caseless_find`]: fn.caseless_find.html
+#[derive(Debug, Clone)]
+pub struct CaselessMatcher {
+    states: Vec<State>,
+    /// The length of each pattern in folded characters
+    pattern_lens: Vec<usize>,
+    max_pattern_len: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    /// Transitions sorted by character
+    transitions: Vec<(char, usize)>,
+    fail: usize,
+    /// Patterns that end at this state, longest first
+    matches: Vec<usize>,
+}
+
+/// A match found by a [`CaselessMatcher`].
+///
+/// [`CaselessMatcher`]: struct.CaselessMatcher.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CaselessMatch {
+    pattern: usize,
+    start: usize,
+    end: usize,
+}
+
+impl CaselessMatch {
+    /// The index of the pattern that matched, in the order supplied to `CaselessMatcher::new`.
+    pub fn pattern(&self) -> usize {
+        self.pattern
+    }
+
+    /// The byte offset of the start of the match in the input.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset of the end of the match in the input.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The byte range of the match in the input.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Deserializing checks that the match does not end before it starts.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CaselessMatch {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "CaselessMatch")]
+        struct Fields {
+            pattern: usize,
+            start: usize,
+            end: usize,
+        }
+
+        let Fields {
+            pattern,
+            start,
+            end,
+        } = Fields::deserialize(deserializer)?;
+        if start <= end {
+            Ok(CaselessMatch {
+                pattern,
+                start,
+                end,
+            })
+        } else {
+            Err(de::Error::custom("caseless match ends before it starts"))
+        }
+    }
+}
+
+impl CaselessMatcher {
+    /// Compile a matcher for `patterns`.
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        let mut states = vec![State::default()];
+        let mut pattern_lens = Vec::new();
+
+        for (pattern, text) in patterns.into_iter().enumerate() {
+            let mut state = ROOT;
+            let mut len = 0;
+            for chr in MapChars::new(&Builtin, text.as_ref().chars(), Case::FullFold) {
+                state = match states[state].next(chr) {
+                    Some(next) => next,
+                    None => {
+                        let next = states.len();
+                        states.push(State::default());
+                        states[state].insert(chr, next);
+                        next
+                    }
+                };
+                len += 1;
+            }
+            if len > 0 {
+                states[state].matches.push(pattern);
+            }
+            pattern_lens.push(len);
+        }
+
+        // Compute the failure links breadth first, so that the links of shorter prefixes are
+        // known before they are needed.
+        let mut queue: VecDeque<usize> = states[ROOT]
+            .transitions
+            .iter()
+            .map(|&(_, next)| next)
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            for i in 0..states[state].transitions.len() {
+                let (chr, next) = states[state].transitions[i];
+                let mut fail = states[state].fail;
+                let fail = loop {
+                    if let Some(target) = states[fail].next(chr) {
+                        break target;
+                    }
+                    if fail == ROOT {
+                        break ROOT;
+                    }
+                    fail = states[fail].fail;
+                };
+                states[next].fail = fail;
+                let inherited = states[fail].matches.clone();
+                states[next].matches.extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        let max_pattern_len = pattern_lens.iter().cloned().max().unwrap_or(0);
+        CaselessMatcher {
+            states,
+            pattern_lens,
+            max_pattern_len,
+        }
+    }
+
+    /// Returns an iterator over all matches in `haystack`, including overlapping matches.
+    ///
+    /// Matches are returned in order of their end offset. Matches that end at the same offset
+    /// are returned longest first.
+    pub fn find_iter<'m, 'h>(&'m self, haystack: &'h str) -> CaselessMatches<'m, 'h> {
+        CaselessMatches {
+            matcher: self,
+            chars: haystack.char_indices(),
+            state: ROOT,
+            position: 0,
+            starts: vec![NOT_BOUNDARY; self.max_pattern_len.max(1)],
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Returns true if any pattern matches `haystack`.
+    pub fn is_match(&self, haystack: &str) -> bool {
+        self.find_iter(haystack).next().is_some()
+    }
+
+    fn next_state(&self, mut state: usize, chr: char) -> usize {
+        loop {
+            if let Some(next) = self.states[state].next(chr) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.states[state].fail;
+        }
+    }
+}
+
+impl State {
+    fn next(&self, chr: char) -> Option<usize> {
+        self.transitions
+            .binary_search_by_key(&chr, |&(c, _)| c)
+            .ok()
+            .map(|index| self.transitions[index].1)
+    }
+
+    fn insert(&mut self, chr: char, next: usize) {
+        let index = self
+            .transitions
+            .binary_search_by_key(&chr, |&(c, _)| c)
+            .unwrap_err();
+        self.transitions.insert(index, (chr, next));
+    }
+}
+
+/// An iterator over the matches of a [`CaselessMatcher`] in a string.
+///
+/// Returned by [`CaselessMatcher::find_iter`].
+///
+/// [`CaselessMatcher`]: struct.CaselessMatcher.html
+/// [`CaselessMatcher::find_iter`]: struct.CaselessMatcher.html#method.find_iter
+#[derive(Debug, Clone)]
+pub struct CaselessMatches<'m, 'h> {
+    matcher: &'m CaselessMatcher,
+    chars: CharIndices<'h>,
+    state: usize,
+    /// The number of folded characters consumed so far
+    position: usize,
+    /// Ring buffer of the input byte offset of each recent folded character, or `NOT_BOUNDARY`
+    /// if it is not the first character of a folded character.
+    starts: Vec<usize>,
+    pending: VecDeque<CaselessMatch>,
+}
+
+impl<'m, 'h> Iterator for CaselessMatches<'m, 'h> {
+    type Item = CaselessMatch;
+
+    fn next(&mut self) -> Option<CaselessMatch> {
+        while self.pending.is_empty() {
+            let (offset, chr) = self.chars.next()?;
+            let end = offset + chr.len_utf8();
+            for (i, folded) in mapped_chars(&Case::FullFold.map(&Builtin, chr)).enumerate() {
+                let ring_len = self.starts.len();
+                self.starts[self.position % ring_len] = if i == 0 { offset } else { NOT_BOUNDARY };
+                self.position += 1;
+                self.state = self.matcher.next_state(self.state, folded);
+            }
+
+            // Only report matches that end on a character boundary of the input
+            let matcher = self.matcher;
+            for &pattern in &matcher.states[self.state].matches {
+                let first = self.position - matcher.pattern_lens[pattern];
+                let start = self.starts[first % self.starts.len()];
+                if start != NOT_BOUNDARY {
+                    self.pending.push_back(CaselessMatch {
+                        pattern,
+                        start,
+                        end,
+                    });
+                }
+            }
+        }
+        self.pending.pop_front()
+    }
+}
@@ -0,0 +1,256 @@
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::case_mapping::{Builtin, Tables};
+use crate::string::{convert_ascii_words, mapped_chars, Case};
+
+/// Convert a string to lowercase, also returning a map between offsets in `s` and the result.
+///
+/// The conversion is the same as [`lowercase_str`].
+///
+/// ### Example
+///
+/// ```
+/// let (lower, offsets) = unicode_case_mapping::lowercase_with_offsets("İA");
+/// assert_eq!(lower, "i\u{307}a");
+/// assert_eq!(offsets.to_output(2), 3);
+/// assert_eq!(offsets.to_source(3), 2);
+///
+/// let (lower, _) = unicode_case_mapping::lowercase_with_offsets("A\0");
+/// assert_eq!(lower, "a\0");
+/// ```
+///
+/// [`lowercase_str`]: fn.lowercase_str.html
+pub fn lowercase_with_offsets(s: &str) -> (String, OffsetMap) {
+    convert_with_offsets(&Builtin, s, Case::Lower)
+}
+
+/// Convert a string to uppercase, also returning a map between offsets in `s` and the result.
+///
+/// The conversion is the same as [`uppercase_str`].
+///
+/// ### Example
+///
+/// ```
+/// // U+0149 LATIN SMALL LETTER N PRECEDED BY APOSTROPHE
+/// let (upper, offsets) = unicode_case_mapping::uppercase_with_offsets("\u{149}a");
+/// assert_eq!(upper, "\u{2BC}NA");
+/// assert_eq!(offsets.to_output(2), 3);
+/// assert_eq!(offsets.to_source(3), 2);
+/// // Offsets within the expansion map to the start of U+0149
+/// assert_eq!(offsets.to_source(2), 0);
+///
+/// // `ß` becomes `SS`, which has the same length but a character boundary in the middle
+/// let (upper, offsets) = unicode_case_mapping::uppercase_with_offsets("straße");
+/// assert_eq!(upper, "STRASSE");
+/// assert_eq!(offsets.to_source(5), 4);
+/// ```
+///
+/// [`uppercase_str`]: fn.uppercase_str.html
+pub fn uppercase_with_offsets(s: &str) -> (String, OffsetMap) {
+    convert_with_offsets(&Builtin, s, Case::Upper)
+}
+
+/// A map between byte offsets in a string and byte offsets in its case converted equivalent.
+///
+/// The map is a list of segments that cover both strings in order. Within a segment where each
+/// character maps to a single character of the same length offsets correspond one-to-one. Other
+/// segments are a single source character whose mapping changed length or expanded to several
+/// characters, such as `İ` → `i̇` or `ß` → `SS`. Offsets inside these segments map to the start
+/// of the corresponding segment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetMap {
+    segments: Vec<OffsetSegment>,
+}
+
+/// A segment of an [`OffsetMap`].
+///
+/// [`OffsetMap`]: struct.OffsetMap.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct OffsetSegment {
+    /// Byte range in the source string
+    pub source: Range<usize>,
+    /// Byte range in the output string
+    pub output: Range<usize>,
+    one_to_one: bool,
+}
+
+impl OffsetSegment {
+    /// Returns true if offsets within the segment correspond one-to-one.
+    pub fn is_one_to_one(&self) -> bool {
+        self.one_to_one
+    }
+
+    /// Returns true if neither range ends before it starts and a one-to-one segment has the same
+    /// length in both strings.
+    #[cfg(feature = "serde")]
+    fn is_valid(&self) -> bool {
+        self.source.start <= self.source.end
+            && self.output.start <= self.output.end
+            && (!self.one_to_one
+                || self.source.end - self.source.start == self.output.end - self.output.start)
+    }
+}
+
+/// Deserializing checks that the ranges are consistent.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for OffsetSegment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "OffsetSegment")]
+        struct Fields {
+            source: Range<usize>,
+            output: Range<usize>,
+            one_to_one: bool,
+        }
+
+        let Fields {
+            source,
+            output,
+            one_to_one,
+        } = Fields::deserialize(deserializer)?;
+        let segment = OffsetSegment {
+            source,
+            output,
+            one_to_one,
+        };
+        if segment.is_valid() {
+            Ok(segment)
+        } else {
+            Err(de::Error::custom(
+                "offset segment ends before it starts or has mismatched lengths",
+            ))
+        }
+    }
+}
+
+impl OffsetMap {
+    /// The segments that make up the map, in order.
+    pub fn segments(&self) -> &[OffsetSegment] {
+        &self.segments
+    }
+
+    /// Translate a byte offset in the source string to the output string.
+    ///
+    /// Offsets past the end of the source map to the end of the output.
+    pub fn to_output(&self, offset: usize) -> usize {
+        self.translate(offset, |segment| (&segment.source, &segment.output))
+    }
+
+    /// Translate a byte offset in the output string to the source string.
+    ///
+    /// Offsets past the end of the output map to the end of the source.
+    pub fn to_source(&self, offset: usize) -> usize {
+        self.translate(offset, |segment| (&segment.output, &segment.source))
+    }
+
+    fn translate<F>(&self, offset: usize, ranges: F) -> usize
+    where
+        F: Fn(&OffsetSegment) -> (&Range<usize>, &Range<usize>),
+    {
+        // Find the last segment that starts at or before offset
+        let index = match self
+            .segments
+            .binary_search_by_key(&offset, |segment| ranges(segment).0.start)
+        {
+            Ok(index) => index,
+            Err(0) => return 0,
+            Err(index) => index - 1,
+        };
+        let segment = &self.segments[index];
+        let (from, to) = ranges(segment);
+        if offset >= from.end {
+            to.end
+        } else if segment.one_to_one {
+            to.start + (offset - from.start)
+        } else {
+            to.start
+        }
+    }
+
+    /// Returns true if the segments cover both strings in order.
+    #[cfg(feature = "serde")]
+    fn is_contiguous(segments: &[OffsetSegment]) -> bool {
+        let mut end = (0, 0);
+        segments.iter().all(|segment| {
+            let contiguous = (segment.source.start, segment.output.start) == end;
+            end = (segment.source.end, segment.output.end);
+            contiguous
+        })
+    }
+
+    fn push(&mut self, source: Range<usize>, output: Range<usize>, one_to_one: bool) {
+        let segment = OffsetSegment {
+            source,
+            output,
+            one_to_one,
+        };
+        if let Some(last) = self.segments.last_mut() {
+            if last.one_to_one && segment.one_to_one {
+                last.source.end = segment.source.end;
+                last.output.end = segment.output.end;
+                return;
+            }
+        }
+        self.segments.push(segment);
+    }
+}
+
+/// Serialized as the sequence of segments. Deserializing checks that the segments are consistent.
+#[cfg(feature = "serde")]
+impl Serialize for OffsetMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.segments.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for OffsetMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let segments = Vec::<OffsetSegment>::deserialize(deserializer)?;
+        if OffsetMap::is_contiguous(&segments) {
+            Ok(OffsetMap { segments })
+        } else {
+            Err(de::Error::custom("offset map segments are not contiguous"))
+        }
+    }
+}
+
+pub(crate) fn convert_with_offsets<T: Tables>(
+    tables: &T,
+    s: &str,
+    case: Case,
+) -> (String, OffsetMap) {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut offsets = OffsetMap::default();
+    let mut i = 0;
+    while i < bytes.len() {
+        let ascii_len = convert_ascii_words(&bytes[i..], &mut out, case);
+        if ascii_len > 0 {
+            let end = out.len();
+            offsets.push(i..i + ascii_len, end - ascii_len..end, true);
+            i += ascii_len;
+            continue;
+        }
+
+        let chr = s[i..].chars().next().unwrap();
+        let start = out.len();
+        let mut buf = [0; 4];
+        let mut count = 0;
+        for mapped in mapped_chars(&case.map(tables, chr)) {
+            out.extend_from_slice(mapped.encode_utf8(&mut buf).as_bytes());
+            count += 1;
+        }
+        let one_to_one = count == 1 && out.len() - start == chr.len_utf8();
+        offsets.push(i..i + chr.len_utf8(), start..out.len(), one_to_one);
+        i += chr.len_utf8();
+    }
+
+    // SAFETY: `out` is made up of whole UTF-8 encoded characters and ASCII bytes, which remain
+    // ASCII after case conversion.
+    (unsafe { String::from_utf8_unchecked(out) }, offsets)
+}
@@ -0,0 +1,646 @@
+use std::num::NonZeroU32;
+use std::{char, mem, str};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ascii;
+use crate::case_mapping::{full_case_folding, simple_mapping, toggle, Builtin, Tables};
+
+/// Convert a string to lowercase.
+///
+/// This is equivalent to mapping each character with [`to_lowercase`], however runs of ASCII
+/// are converted several bytes at a time and only non-ASCII characters are looked up in the
+/// mapping tables. Context sensitive mappings, such as final sigma, are not applied.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::lowercase_str("HELLO İSTANBUL"), "hello i\u{307}stanbul");
+/// ```
+///
+/// [`to_lowercase`]: fn.to_lowercase.html
+pub fn lowercase_str(s: &str) -> String {
+    convert_str(&Builtin, s, Case::Lower, LengthPolicy::Unrestricted)
+}
+
+/// Convert a string to uppercase.
+///
+/// This is equivalent to mapping each character with [`to_uppercase`], however runs of ASCII
+/// are converted several bytes at a time and only non-ASCII characters are looked up in the
+/// mapping tables.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::uppercase_str("Straße"), "STRASSE");
+/// ```
+///
+/// [`to_uppercase`]: fn.to_uppercase.html
+pub fn uppercase_str(s: &str) -> String {
+    convert_str(&Builtin, s, Case::Upper, LengthPolicy::Unrestricted)
+}
+
+/// Convert a string to lowercase, restricting changes to its length according to `policy`.
+///
+/// With [`LengthPolicy::Unrestricted`] this is the same as [`lowercase_str`].
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::LengthPolicy;
+///
+/// let lower = unicode_case_mapping::lowercase_str_with("İSTANBUL", LengthPolicy::PreserveChars);
+/// assert_eq!(lower, "istanbul");
+/// // The simple mapping of İ changes the UTF-8 length so İ is left unchanged
+/// let lower = unicode_case_mapping::lowercase_str_with("İSTANBUL", LengthPolicy::PreserveUtf8Len);
+/// assert_eq!(lower, "İstanbul");
+/// ```
+///
+/// [`LengthPolicy::Unrestricted`]: enum.LengthPolicy.html#variant.Unrestricted
+/// [`lowercase_str`]: fn.lowercase_str.html
+pub fn lowercase_str_with(s: &str, policy: LengthPolicy) -> String {
+    convert_str(&Builtin, s, Case::Lower, policy)
+}
+
+/// Convert a string to uppercase, restricting changes to its length according to `policy`.
+///
+/// With [`LengthPolicy::Unrestricted`] this is the same as [`uppercase_str`].
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::LengthPolicy;
+///
+/// let upper = unicode_case_mapping::uppercase_str_with("straße", LengthPolicy::PreserveChars);
+/// assert_eq!(upper, "STRAẞE");
+/// // SS is the same length as ß when encoded as UTF-8, but I is shorter than ı
+/// let upper = unicode_case_mapping::uppercase_str_with("straße ı", LengthPolicy::PreserveUtf8Len);
+/// assert_eq!(upper, "STRASSE ı");
+/// ```
+///
+/// [`LengthPolicy::Unrestricted`]: enum.LengthPolicy.html#variant.Unrestricted
+/// [`uppercase_str`]: fn.uppercase_str.html
+pub fn uppercase_str_with(s: &str, policy: LengthPolicy) -> String {
+    convert_str(&Builtin, s, Case::Upper, policy)
+}
+
+/// Restrictions on how a case conversion may change the length of text.
+///
+/// This is useful for text with a fixed budget, such as form fields and terminal cells. A policy
+/// is only honored by [`lowercase_str_with`] and [`uppercase_str_with`], and the `CaseData`
+/// methods of the same names. The other conversions apply the full mappings, except
+/// [`lowercase_str_in_place`] and [`uppercase_str_in_place`], which always preserve the UTF-8
+/// length.
+///
+/// [`lowercase_str_with`]: fn.lowercase_str_with.html
+/// [`uppercase_str_with`]: fn.uppercase_str_with.html
+/// [`lowercase_str_in_place`]: fn.lowercase_str_in_place.html
+/// [`uppercase_str_in_place`]: fn.uppercase_str_in_place.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LengthPolicy {
+    /// Apply the full mappings, which may change the length of the text.
+    Unrestricted,
+    /// Preserve the number of characters. Where the full mapping of a character expands to more
+    /// than one character its simple mapping is used instead, such as `ß` → `ẞ` when uppercasing.
+    PreserveChars,
+    /// Preserve the UTF-8 encoded length. Where the full mapping of a character changes the
+    /// number of bytes its simple mapping is used instead, if that has the same length.
+    /// Otherwise the character is left unchanged.
+    PreserveUtf8Len,
+}
+
+impl Default for LengthPolicy {
+    fn default() -> Self {
+        LengthPolicy::Unrestricted
+    }
+}
+
+/// Swap the case of each character in a string.
+///
+/// Each character is mapped as per [`toggle_case`], so uppercase and titlecase characters
+/// become lowercase and lowercase characters become uppercase.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::swapcase_str("Hello Straße ǅ"), "hELLO sTRASSE ǆ");
+/// ```
+///
+/// [`toggle_case`]: fn.toggle_case.html
+pub fn swapcase_str(s: &str) -> String {
+    convert_str(&Builtin, s, Case::Toggle, LengthPolicy::Unrestricted)
+}
+
+/// Apply full case folding to a string.
+///
+/// Characters are folded as by [`caseless_eq`], which uses the full case folding (statuses C and
+/// F in CaseFolding.txt), so `ß` folds to `ss`. This is the same folding as Python's
+/// `str.casefold`, for the same version of Unicode. Use [`case_fold_bytes`] for simple case
+/// folding, which never changes the number of characters.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::full_case_fold_str("Straße ΣΊΣΥΦΟΣ"), "strasse σίσυφοσ");
+/// ```
+///
+/// [`caseless_eq`]: fn.caseless_eq.html
+/// [`case_fold_bytes`]: fn.case_fold_bytes.html
+pub fn full_case_fold_str(s: &str) -> String {
+    convert_str(&Builtin, s, Case::FullFold, LengthPolicy::Unrestricted)
+}
+
+/// Convert the first letter of each word in a string to titlecase and the rest to lowercase.
+///
+/// Words are separated by whitespace. The first letter or digit of each word is mapped as per
+/// [`to_titlecase`] and the following characters as per [`to_lowercase`]. Punctuation within a
+/// word, such as an apostrophe, does not start a new word.
+///
+/// ### Example
+///
+/// ```
+/// assert_eq!(unicode_case_mapping::titlecase_str("ǆEMAL o'NEILL ßeta"), "ǅemal O'neill Sseta");
+/// ```
+///
+/// [`to_titlecase`]: fn.to_titlecase.html
+/// [`to_lowercase`]: fn.to_lowercase.html
+pub fn titlecase_str(s: &str) -> String {
+    convert_titlecase(&Builtin, s)
+}
+
+/// Convert `s` to titlecase as per `titlecase_str`, using `tables`.
+pub(crate) fn convert_titlecase<T: Tables>(tables: &T, s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    Titlecaser::new().convert_with(tables, s, &mut out);
+    out
+}
+
+/// Converts text to titlecase as per [`titlecase_str`] a piece at a time.
+///
+/// The titlecaser remembers whether the next character starts a word, so text that is read in
+/// pieces, such as line by line, is converted the same as if it were converted all at once.
+///
+/// ### Example
+///
+/// ```
+/// use unicode_case_mapping::Titlecaser;
+///
+/// let mut titlecaser = Titlecaser::new();
+/// let mut out = String::new();
+/// titlecaser.convert("hELLO wO", &mut out);
+/// titlecaser.convert("RLD", &mut out);
+/// assert_eq!(out, "Hello World");
+/// ```
+///
+/// [`titlecase_str`]: fn.titlecase_str.html
+#[derive(Debug, Clone)]
+pub struct Titlecaser {
+    /// True if the next letter or digit starts a word
+    word_start: bool,
+}
+
+impl Titlecaser {
+    /// Create a titlecaser for text that starts at the beginning of a word.
+    pub fn new() -> Self {
+        Titlecaser { word_start: true }
+    }
+
+    /// Convert the next piece of text, appending the result to `out`.
+    pub fn convert(&mut self, s: &str, out: &mut String) {
+        self.convert_with(&Builtin, s, out)
+    }
+
+    fn convert_with<T: Tables>(&mut self, tables: &T, s: &str, out: &mut String) {
+        for chr in s.chars() {
+            if self.word_start {
+                let title = tables.lookup(chr as u32).2;
+                if title[0] == 0 {
+                    out.push(chr);
+                } else {
+                    out.extend(mapped_chars(&title));
+                }
+            } else {
+                out.extend(mapped_chars(&Case::Lower.map(tables, chr)));
+            }
+
+            if chr.is_alphanumeric() {
+                self.word_start = false;
+            } else if chr.is_whitespace() {
+                self.word_start = true;
+            }
+        }
+    }
+}
+
+impl Default for Titlecaser {
+    fn default() -> Self {
+        Titlecaser::new()
+    }
+}
+
+/// Convert a string to lowercase in place.
+///
+/// Characters are rewritten in the existing buffer while their lowercase equivalents have the
+/// same UTF-8 length or shorter. The buffer only grows when a mapping expands, such as
+/// `İ` → `i̇`. The result is the same as [`lowercase_str`].
+///
+/// ### Example
+///
+/// ```
+/// let mut s = String::from("ΑΒΓ DEF");
+/// unicode_case_mapping::lowercase_in_place(&mut s);
+/// assert_eq!(s, "αβγ def");
+/// ```
+///
+/// [`lowercase_str`]: fn.lowercase_str.html
+pub fn lowercase_in_place(s: &mut String) {
+    convert_in_place(&Builtin, s, Case::Lower)
+}
+
+/// Convert a string to uppercase in place.
+///
+/// Characters are rewritten in the existing buffer while their uppercase equivalents have the
+/// same UTF-8 length or shorter. The buffer only grows when a mapping expands, such as
+/// `ß` → `SS`. The result is the same as [`uppercase_str`].
+///
+/// ### Example
+///
+/// ```
+/// let mut s = String::from("große");
+/// unicode_case_mapping::uppercase_in_place(&mut s);
+/// assert_eq!(s, "GROSSE");
+/// ```
+///
+/// [`uppercase_str`]: fn.uppercase_str.html
+pub fn uppercase_in_place(s: &mut String) {
+    convert_in_place(&Builtin, s, Case::Upper)
+}
+
+/// Convert a string slice to lowercase in place, without changing its length.
+///
+/// Characters whose lowercase equivalent has a different UTF-8 length are mapped as per
+/// [`LengthPolicy::PreserveUtf8Len`], so the result is the same as [`lowercase_str_with`] with
+/// that policy. Use [`lowercase_in_place`] to apply the full mappings to a `String`.
+///
+/// ### Example
+///
+/// ```
+/// let mut s = String::from("ΑΒΓ İSTANBUL");
+/// unicode_case_mapping::lowercase_str_in_place(s.as_mut_str());
+/// assert_eq!(s, "αβγ İstanbul");
+/// ```
+///
+/// [`LengthPolicy::PreserveUtf8Len`]: enum.LengthPolicy.html#variant.PreserveUtf8Len
+/// [`lowercase_str_with`]: fn.lowercase_str_with.html
+/// [`lowercase_in_place`]: fn.lowercase_in_place.html
+pub fn lowercase_str_in_place(s: &mut str) {
+    convert_str_in_place(&Builtin, s, Case::Lower)
+}
+
+/// Convert a string slice to uppercase in place, without changing its length.
+///
+/// Characters whose uppercase equivalent has a different UTF-8 length are mapped as per
+/// [`LengthPolicy::PreserveUtf8Len`], so the result is the same as [`uppercase_str_with`] with
+/// that policy. Use [`uppercase_in_place`] to apply the full mappings to a `String`.
+///
+/// ### Example
+///
+/// ```
+/// let mut s = String::from("straße ı");
+/// unicode_case_mapping::uppercase_str_in_place(s.as_mut_str());
+/// assert_eq!(s, "STRASSE ı");
+/// ```
+///
+/// [`LengthPolicy::PreserveUtf8Len`]: enum.LengthPolicy.html#variant.PreserveUtf8Len
+/// [`uppercase_str_with`]: fn.uppercase_str_with.html
+/// [`uppercase_in_place`]: fn.uppercase_in_place.html
+pub fn uppercase_str_in_place(s: &mut str) {
+    convert_str_in_place(&Builtin, s, Case::Upper)
+}
+
+/// The case a string-level conversion maps characters to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Case {
+    Lower,
+    Upper,
+    /// Simple case folding
+    Fold,
+    /// Full case folding
+    FullFold,
+    /// Toggle between uppercase and lowercase
+    Toggle,
+}
+
+impl Case {
+    /// Convert an all-ASCII word.
+    #[inline]
+    pub(crate) fn convert_word(self, word: u64) -> u64 {
+        match self {
+            Case::Lower | Case::Fold | Case::FullFold => ascii::lowercase_word(word),
+            Case::Upper => ascii::uppercase_word(word),
+            Case::Toggle => ascii::toggle_word(word),
+        }
+    }
+
+    /// Convert a single ASCII byte.
+    #[inline]
+    pub(crate) fn convert_ascii(self, byte: u8) -> u8 {
+        match self {
+            Case::Lower | Case::Fold | Case::FullFold => byte.to_ascii_lowercase(),
+            Case::Upper => byte.to_ascii_uppercase(),
+            Case::Toggle if byte.is_ascii_alphabetic() => byte ^ 0x20,
+            Case::Toggle => byte,
+        }
+    }
+
+    /// Map `chr`, returning the resulting code points padded with zeros.
+    ///
+    /// Unlike the table data, a character that maps to itself is returned as itself rather than
+    /// all zeros.
+    #[inline]
+    pub(crate) fn map<T: Tables>(self, tables: &T, chr: char) -> [u32; 3] {
+        let mut mapped = [0; 3];
+        match self {
+            Case::Lower => mapped[..2].copy_from_slice(&tables.lookup(chr as u32).0),
+            Case::Upper => mapped = tables.lookup(chr as u32).1,
+            Case::Fold => {
+                mapped[0] = tables
+                    .case_folded_u32(chr as u32)
+                    .map_or(0, NonZeroU32::get)
+            }
+            Case::FullFold => mapped = full_case_folding(tables, chr),
+            Case::Toggle => mapped = toggle(tables.lookup(chr as u32)),
+        }
+        if mapped[0] == 0 {
+            mapped[0] = chr as u32;
+        }
+        mapped
+    }
+
+    /// Map `chr` as per `map`, restricting changes to its length according to `policy`.
+    #[inline]
+    pub(crate) fn map_with_policy<T: Tables>(
+        self,
+        tables: &T,
+        chr: char,
+        policy: LengthPolicy,
+    ) -> [u32; 3] {
+        let mapped = self.map(tables, chr);
+        let preserved = match policy {
+            LengthPolicy::Unrestricted => true,
+            LengthPolicy::PreserveChars => mapped[1] == 0,
+            LengthPolicy::PreserveUtf8Len => utf8_len(&mapped) == chr.len_utf8(),
+        };
+        if preserved {
+            return mapped;
+        }
+
+        let simple = self.map_simple(tables, chr);
+        if policy == LengthPolicy::PreserveUtf8Len && simple.len_utf8() != chr.len_utf8() {
+            [chr as u32, 0, 0]
+        } else {
+            [simple as u32, 0, 0]
+        }
+    }
+
+    /// Map `chr` to a single character.
+    fn map_simple<T: Tables>(self, tables: &T, chr: char) -> char {
+        let folded = || {
+            tables
+                .case_folded_u32(chr as u32)
+                .and_then(|folded| char::from_u32(folded.get()))
+                .unwrap_or(chr)
+        };
+        match self {
+            Case::Lower => simple_mapping(tables, chr, 0),
+            Case::Upper => {
+                // Characters like ß have no simple uppercase mapping, however there may be an
+                // uppercase character that is equivalent under case folding.
+                let upper = simple_mapping(tables, chr, 1);
+                if upper != chr {
+                    return upper;
+                }
+                tables
+                    .chars_folding_to(folded())
+                    .iter()
+                    .cloned()
+                    .find(|&other| {
+                        let (lower, upper, _) = tables.lookup(other as u32);
+                        other != chr && lower[0] != 0 && upper[0] == 0
+                    })
+                    .unwrap_or(chr)
+            }
+            Case::Fold | Case::FullFold => folded(),
+            Case::Toggle if tables.lookup(chr as u32).0[0] != 0 => {
+                Case::Lower.map_simple(tables, chr)
+            }
+            Case::Toggle => Case::Upper.map_simple(tables, chr),
+        }
+    }
+}
+
+/// The UTF-8 encoded length of a mapping returned by `Case::map`.
+fn utf8_len(mapped: &[u32]) -> usize {
+    mapped_chars(mapped).map(char::len_utf8).sum()
+}
+
+/// Iterate over the characters of a mapping returned by `Case::map`.
+///
+/// The first code point is always present, even if it is U+0000, which maps to itself.
+pub(crate) fn mapped_chars(mapped: &[u32]) -> impl Iterator<Item = char> + '_ {
+    let (first, rest) = mapped.split_at(1);
+    first
+        .iter()
+        .chain(rest.iter().take_while(|&&cp| cp != 0))
+        .map(|&cp| char::from_u32(cp).expect("invalid code point in case mapping table"))
+}
+
+/// An iterator over characters converted to a particular case.
+#[derive(Debug, Clone)]
+pub(crate) struct MapChars<'t, I, T = Builtin> {
+    chars: I,
+    tables: &'t T,
+    case: Case,
+    mapped: [u32; 3],
+    index: usize,
+}
+
+impl<'t, I: Iterator<Item = char>, T: Tables> MapChars<'t, I, T> {
+    pub(crate) fn new(tables: &'t T, chars: I, case: Case) -> Self {
+        MapChars {
+            chars,
+            tables,
+            case,
+            mapped: [0; 3],
+            index: 3,
+        }
+    }
+}
+
+impl<'t, I: Iterator<Item = char>, T: Tables> Iterator for MapChars<'t, I, T> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if let Some(&cp) = self.mapped.get(self.index) {
+            if cp != 0 {
+                self.index += 1;
+                return char::from_u32(cp);
+            }
+        }
+
+        let chr = self.chars.next()?;
+        if chr.is_ascii() {
+            return Some(char::from(self.case.convert_ascii(chr as u8)));
+        }
+        self.mapped = self.case.map(self.tables, chr);
+        self.index = 1;
+        char::from_u32(self.mapped[0])
+    }
+}
+
+pub(crate) fn convert_str<T: Tables>(
+    tables: &T,
+    s: &str,
+    case: Case,
+    policy: LengthPolicy,
+) -> String {
+    let mut out = Vec::with_capacity(s.len());
+    convert_str_into(tables, s, &mut out, case, policy);
+    // SAFETY: `convert_str_into` only appends whole UTF-8 encoded characters.
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Convert `s`, appending the UTF-8 encoded result to `out`.
+pub(crate) fn convert_str_into<T: Tables>(
+    tables: &T,
+    s: &str,
+    out: &mut Vec<u8>,
+    case: Case,
+    policy: LengthPolicy,
+) {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let ascii_len = convert_ascii_words(&bytes[i..], out, case);
+        i += ascii_len;
+        if i == bytes.len() {
+            break;
+        }
+        if bytes[i].is_ascii() {
+            out.push(case.convert_ascii(bytes[i]));
+            i += 1;
+        } else {
+            let chr = s[i..].chars().next().unwrap();
+            let mut buf = [0; 4];
+            for mapped in mapped_chars(&case.map_with_policy(tables, chr, policy)) {
+                out.extend_from_slice(mapped.encode_utf8(&mut buf).as_bytes());
+            }
+            i += chr.len_utf8();
+        }
+    }
+}
+
+pub(crate) fn convert_in_place<T: Tables>(tables: &T, s: &mut String, case: Case) {
+    // Work on the bytes of the string so that if anything panics the caller is left with an
+    // empty string rather than a partially rewritten one.
+    let mut bytes = mem::replace(s, String::new()).into_bytes();
+    let mut read = 0;
+    let mut write = 0;
+    while read < bytes.len() {
+        if let Some(word) = ascii::read_ascii_word(&bytes[read..]) {
+            let converted = case.convert_word(word).to_ne_bytes();
+            bytes[write..write + ascii::WORD_SIZE].copy_from_slice(&converted);
+            read += ascii::WORD_SIZE;
+            write += ascii::WORD_SIZE;
+            continue;
+        }
+
+        let chr = decode_char(&bytes[read..]);
+        let mut buf = [0; 12];
+        let mut len = 0;
+        for mapped in mapped_chars(&case.map(tables, chr)) {
+            len += mapped.encode_utf8(&mut buf[len..]).len();
+        }
+
+        if write + len > read + chr.len_utf8() {
+            // The mapping expands past the bytes still to be read. Convert the remainder
+            // separately and append it to what has been written so far.
+            let rest = convert_str(
+                tables,
+                str::from_utf8(&bytes[read..]).unwrap(),
+                case,
+                LengthPolicy::Unrestricted,
+            );
+            bytes.truncate(write);
+            bytes.extend_from_slice(rest.as_bytes());
+            write = bytes.len();
+            break;
+        }
+
+        bytes[write..write + len].copy_from_slice(&buf[..len]);
+        read += chr.len_utf8();
+        write += len;
+    }
+    bytes.truncate(write);
+
+    // SAFETY: `bytes` is made up of whole UTF-8 encoded characters written by the loop above.
+    *s = unsafe { String::from_utf8_unchecked(bytes) };
+}
+
+pub(crate) fn convert_str_in_place<T: Tables>(tables: &T, s: &mut str, case: Case) {
+    // SAFETY: each character is only overwritten by a character with the same UTF-8 length, so
+    // the bytes remain valid UTF-8.
+    let bytes = unsafe { s.as_bytes_mut() };
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(word) = ascii::read_ascii_word(&bytes[i..]) {
+            let converted = case.convert_word(word).to_ne_bytes();
+            bytes[i..i + ascii::WORD_SIZE].copy_from_slice(&converted);
+            i += ascii::WORD_SIZE;
+            continue;
+        }
+
+        let chr = decode_char(&bytes[i..]);
+        let mut buf = [0; 12];
+        let mut len = 0;
+        let mapped = case.map_with_policy(tables, chr, LengthPolicy::PreserveUtf8Len);
+        for mapped in mapped_chars(&mapped) {
+            len += mapped.encode_utf8(&mut buf[len..]).len();
+        }
+        if len == chr.len_utf8() {
+            bytes[i..i + len].copy_from_slice(&buf[..len]);
+        }
+        i += chr.len_utf8();
+    }
+}
+
+/// Decode the character at the start of `bytes`, which must begin with a valid UTF-8 sequence.
+pub(crate) fn decode_char(bytes: &[u8]) -> char {
+    let width = match bytes[0] {
+        0x00..=0x7F => 1,
+        0x80..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    };
+    str::from_utf8(&bytes[..width])
+        .ok()
+        .and_then(|s| s.chars().next())
+        .expect("invalid UTF-8")
+}
+
+/// Convert the leading words of `bytes` that are entirely ASCII, appending them to `out`.
+///
+/// Returns the number of bytes converted.
+pub(crate) fn convert_ascii_words(bytes: &[u8], out: &mut Vec<u8>, case: Case) -> usize {
+    let mut len = 0;
+    for chunk in bytes.chunks_exact(ascii::WORD_SIZE) {
+        match ascii::read_ascii_word(chunk) {
+            Some(word) => out.extend_from_slice(&case.convert_word(word).to_ne_bytes()),
+            None => break,
+        }
+        len += ascii::WORD_SIZE;
+    }
+    len
+}
@@ -0,0 +1,58 @@
+use std::char;
+
+use crate::case_mapping::{Builtin, Tables};
+use crate::string::{mapped_chars, Case};
+
+/// Convert UTF-16 text to lowercase, appending the result to `out`.
+///
+/// Surrogate pairs are decoded so that supplementary plane characters, such as those in the
+/// Deseret, Osage, and Adlam scripts, are mapped like any other character. Unpaired surrogates
+/// are passed through unchanged.
+///
+/// ### Example
+///
+/// ```
+/// // U+10400 DESERET CAPITAL LETTER LONG I, followed by an unpaired surrogate
+/// let input = [0xD801, 0xDC00, 0x0041, 0xD800];
+/// let mut out = Vec::new();
+/// unicode_case_mapping::lowercase_utf16(&input, &mut out);
+/// // U+10428 DESERET SMALL LETTER LONG I
+/// assert_eq!(out, [0xD801, 0xDC28, 0x0061, 0xD800]);
+/// ```
+pub fn lowercase_utf16(input: &[u16], out: &mut Vec<u16>) {
+    convert_utf16(&Builtin, input, out, Case::Lower)
+}
+
+/// Convert UTF-16 text to uppercase, appending the result to `out`.
+///
+/// Surrogate pairs are decoded so that supplementary plane characters, such as those in the
+/// Deseret, Osage, and Adlam scripts, are mapped like any other character. Unpaired surrogates
+/// are passed through unchanged.
+///
+/// ### Example
+///
+/// ```
+/// let input: Vec<u16> = "straße".encode_utf16().collect();
+/// let mut out = Vec::new();
+/// unicode_case_mapping::uppercase_utf16(&input, &mut out);
+/// assert_eq!(String::from_utf16(&out).unwrap(), "STRASSE");
+/// ```
+pub fn uppercase_utf16(input: &[u16], out: &mut Vec<u16>) {
+    convert_utf16(&Builtin, input, out, Case::Upper)
+}
+
+pub(crate) fn convert_utf16<T: Tables>(tables: &T, input: &[u16], out: &mut Vec<u16>, case: Case) {
+    out.reserve(input.len());
+    let mut buf = [0; 2];
+    for result in char::decode_utf16(input.iter().cloned()) {
+        match result {
+            Ok(chr) if chr.is_ascii() => out.push(u16::from(case.convert_ascii(chr as u8))),
+            Ok(chr) => {
+                for mapped in mapped_chars(&case.map(tables, chr)) {
+                    out.extend_from_slice(mapped.encode_utf16(&mut buf));
+                }
+            }
+            Err(err) => out.push(err.unpaired_surrogate()),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! WebAssembly bindings, enabled with the `wasm` feature.
+//!
+//! The functions are exported with [wasm-bindgen] under short JavaScript names, for example
+//! `lowercase("İ")`. They apply the same mappings as the corresponding Rust functions and use the
+//! same tables, so no additional data is added to the module.
+//!
+//! [wasm-bindgen]: https://rustwasm.github.io/docs/wasm-bindgen/
+
+use wasm_bindgen::prelude::*;
+
+/// Convert a string to lowercase, as per `lowercase_str`.
+#[wasm_bindgen(js_name = lowercase)]
+pub fn lowercase(s: &str) -> String {
+    crate::lowercase_str(s)
+}
+
+/// Convert a string to uppercase, as per `uppercase_str`.
+#[wasm_bindgen(js_name = uppercase)]
+pub fn uppercase(s: &str) -> String {
+    crate::uppercase_str(s)
+}
+
+/// Convert the first letter of each word to titlecase and the rest to lowercase, as per
+/// `titlecase_str`.
+#[wasm_bindgen(js_name = titlecase)]
+pub fn titlecase(s: &str) -> String {
+    crate::titlecase_str(s)
+}
+
+/// Apply full case folding to a string, as per `full_case_fold_str`. This is the folding used by
+/// `caseless_eq` and Python's `str.casefold`, so "ß" folds to "ss".
+#[wasm_bindgen(js_name = fold)]
+pub fn fold(s: &str) -> String {
+    crate::full_case_fold_str(s)
+}
+
+/// The version of Unicode the tables were generated from, such as `"16.0.0"`.
+#[wasm_bindgen(js_name = unicodeVersion)]
+pub fn unicode_version() -> String {
+    let (major, minor, update) = crate::UNICODE_VERSION;
+    format!("{}.{}.{}", major, minor, update)
+}
@@ -0,0 +1,40 @@
+/* Checks the C ABI through the generated header. Run with `make test-ffi`. */
+
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "unicode_case_mapping.h"
+
+int main(void) {
+    uint32_t version[3];
+    ucm_unicode_version(version);
+    assert(version[0] >= 16);
+
+    uint32_t out[3];
+    assert(ucm_to_lowercase(0x0130, out) == 2);
+    assert(out[0] == 'i' && out[1] == 0x0307 && out[2] == 0);
+    assert(ucm_to_uppercase(0x00DF, out) == 2);
+    assert(out[0] == 'S' && out[1] == 'S' && out[2] == 0);
+    assert(ucm_to_titlecase(0x01C6, out) == 1);
+    assert(out[0] == 0x01C5);
+    assert(ucm_to_uppercase('-', out) == 0);
+    assert(out[0] == 0 && out[1] == 0 && out[2] == 0);
+    assert(ucm_case_folded(0x1E9E) == 0x00DF);
+    assert(ucm_case_folded(0xD800) == 0);
+
+    const char *input = "Stra\xC3\x9F" "e\xFF";
+    uint8_t buf[16];
+    size_t len = ucm_uppercase_utf8((const uint8_t *)input, strlen(input), NULL, 0,
+                                    UCM_INVALID_UTF8_REPLACE);
+    assert(len == 10);
+    assert(ucm_uppercase_utf8((const uint8_t *)input, strlen(input), buf, sizeof(buf),
+                              UCM_INVALID_UTF8_REPLACE) == len);
+    assert(memcmp(buf, "STRASSE\xEF\xBF\xBD", len) == 0);
+    len = ucm_lowercase_utf8((const uint8_t *)input, strlen(input), buf, sizeof(buf),
+                             UCM_INVALID_UTF8_PASS_THROUGH);
+    assert(len == 8 && memcmp(buf, "stra\xC3\x9F" "e\xFF", len) == 0);
+
+    printf("ok\n");
+    return 0;
+}
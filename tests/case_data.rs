@@ -0,0 +1,285 @@
+//! Tests of loading case data at run time with `CaseData`.
+
+use std::char;
+
+use unicode_case_mapping::*;
+
+const HEADER_LEN: usize = 40;
+
+/// The CRC-32 used by zlib, to fix up the checksum of modified data.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn with_checksum(mut bytes: Vec<u8>) -> Vec<u8> {
+    let len = bytes.len() - 4;
+    let checksum = crc32(&bytes[..len]);
+    bytes[len..].copy_from_slice(&checksum.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn matches_builtin_tables() {
+    let data = CaseData::from_bytes(&CaseData::builtin().to_bytes()).unwrap();
+    assert_eq!(data, CaseData::builtin());
+    assert_eq!(data.unicode_version(), UNICODE_VERSION);
+
+    for cp in 0..=0x11_0000 {
+        assert_eq!(
+            data.to_lowercase_u32(cp),
+            to_lowercase_u32(cp),
+            "{:04X}",
+            cp
+        );
+        assert_eq!(
+            data.to_uppercase_u32(cp),
+            to_uppercase_u32(cp),
+            "{:04X}",
+            cp
+        );
+        assert_eq!(
+            data.to_titlecase_u32(cp),
+            to_titlecase_u32(cp),
+            "{:04X}",
+            cp
+        );
+        assert_eq!(data.case_folded_u32(cp), case_folded_u32(cp), "{:04X}", cp);
+        if let Some(chr) = char::from_u32(cp) {
+            assert_eq!(data.simple_lowercase(chr), simple_lowercase(chr));
+            assert_eq!(data.simple_uppercase(chr), simple_uppercase(chr));
+            assert_eq!(data.simple_titlecase(chr), simple_titlecase(chr));
+            assert_eq!(data.toggle_case(chr), toggle_case(chr));
+        }
+    }
+}
+
+#[test]
+fn rejects_invalid_headers() {
+    let bytes = CaseData::builtin().to_bytes();
+
+    assert_eq!(CaseData::from_bytes(b""), Err(CaseDataError::NotCaseData));
+    assert_eq!(
+        CaseData::from_bytes(&bytes[1..]),
+        Err(CaseDataError::NotCaseData)
+    );
+
+    let mut newer = bytes.clone();
+    newer[8] = 2;
+    assert_eq!(
+        CaseData::from_bytes(&newer),
+        Err(CaseDataError::UnsupportedFormat(2))
+    );
+
+    assert_eq!(
+        CaseData::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(CaseDataError::InvalidLength)
+    );
+    // A count so large that the expected length overflows
+    let mut huge = bytes.clone();
+    huge[20..24].copy_from_slice(&u32::max_value().to_le_bytes());
+    assert_eq!(
+        CaseData::from_bytes(&huge),
+        Err(CaseDataError::InvalidLength)
+    );
+}
+
+#[test]
+fn rejects_corrupt_data() {
+    let bytes = CaseData::builtin().to_bytes();
+    for &offset in &[HEADER_LEN, bytes.len() / 2, bytes.len() - 1] {
+        let mut corrupt = bytes.clone();
+        corrupt[offset] ^= 0x10;
+        assert_eq!(
+            CaseData::from_bytes(&corrupt),
+            Err(CaseDataError::ChecksumMismatch)
+        );
+    }
+}
+
+#[test]
+fn rejects_inconsistent_tables() {
+    let bytes = CaseData::builtin().to_bytes();
+    let count = |index: usize| {
+        let pos = 20 + index * 4;
+        let mut count = [0; 4];
+        count.copy_from_slice(&bytes[pos..pos + 4]);
+        u32::from_le_bytes(count) as usize
+    };
+    let records = HEADER_LEN;
+    let blocks = records + count(0) * 32 + count(1) * 16 + count(2) * 8;
+    let block_offsets = blocks + count(3) * 2;
+
+    // The first record must be empty
+    let mut modified = bytes.clone();
+    modified[records] = b'A';
+    // A mapping to a surrogate
+    let mut surrogate = bytes.clone();
+    surrogate[records + 32..records + 36].copy_from_slice(&0xD800u32.to_le_bytes());
+    // A block entry referring to a record past the end
+    let mut record_index = bytes.clone();
+    record_index[blocks..blocks + 2].copy_from_slice(&(count(0) as u16).to_le_bytes());
+    // A block offset past the end of the blocks
+    let mut block_offset = bytes.clone();
+    block_offset[block_offsets..block_offsets + 2]
+        .copy_from_slice(&(count(3) as u16).to_le_bytes());
+    // A last code point outside the block offsets
+    let mut last_codepoint = bytes.clone();
+    last_codepoint[16..20].copy_from_slice(&0x10FFFFu32.to_le_bytes());
+
+    for modified in vec![
+        modified,
+        surrogate,
+        record_index,
+        block_offset,
+        last_codepoint,
+    ] {
+        assert_eq!(
+            CaseData::from_bytes(&with_checksum(modified)),
+            Err(CaseDataError::InvalidTables)
+        );
+    }
+}
+
+#[test]
+fn string_conversions_match_builtin() {
+    let data = CaseData::from_bytes(&CaseData::builtin().to_bytes()).unwrap();
+    let samples = [
+        "",
+        "Hello, World!",
+        "Die Straße in İSTANBUL",
+        "ΣΊΣΥΦΟΣ σίσυφος",
+        "ǆEMAL o'NEILL ǅ",
+        "ﬃ ŉ ΐ ᾳ ẞ",
+        "\u{10400}\u{104B0}\u{1E900}",
+        "a\0b",
+    ];
+    for &s in &samples {
+        assert_eq!(data.lowercase_str(s), lowercase_str(s));
+        assert_eq!(data.uppercase_str(s), uppercase_str(s));
+        assert_eq!(data.titlecase_str(s), titlecase_str(s));
+        assert_eq!(data.swapcase_str(s), swapcase_str(s));
+        assert_eq!(data.full_case_fold_str(s), full_case_fold_str(s));
+        for &policy in &[LengthPolicy::PreserveChars, LengthPolicy::PreserveUtf8Len] {
+            assert_eq!(
+                data.lowercase_str_with(s, policy),
+                lowercase_str_with(s, policy)
+            );
+            assert_eq!(
+                data.uppercase_str_with(s, policy),
+                uppercase_str_with(s, policy)
+            );
+        }
+
+        let mut bytes = s.as_bytes().to_vec();
+        bytes.push(0xFF);
+        let (mut ours, mut expected) = (Vec::new(), Vec::new());
+        data.lowercase_bytes(&bytes, InvalidUtf8::Replace, &mut ours);
+        lowercase_bytes(&bytes, InvalidUtf8::Replace, &mut expected);
+        data.uppercase_bytes(&bytes, InvalidUtf8::PassThrough, &mut ours);
+        uppercase_bytes(&bytes, InvalidUtf8::PassThrough, &mut expected);
+        data.case_fold_bytes(&bytes, InvalidUtf8::Replace, &mut ours);
+        case_fold_bytes(&bytes, InvalidUtf8::Replace, &mut expected);
+        assert_eq!(ours, expected);
+
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let (mut ours, mut expected) = (Vec::new(), Vec::new());
+        data.lowercase_utf16(&utf16, &mut ours);
+        lowercase_utf16(&utf16, &mut expected);
+        data.uppercase_utf16(&utf16, &mut ours);
+        uppercase_utf16(&utf16, &mut expected);
+        assert_eq!(ours, expected);
+
+        let (mut ours, mut expected) = (s.to_string(), s.to_string());
+        data.lowercase_in_place(&mut ours);
+        lowercase_in_place(&mut expected);
+        assert_eq!(ours, expected);
+        data.uppercase_in_place(&mut ours);
+        uppercase_in_place(&mut expected);
+        assert_eq!(ours, expected);
+        let (mut ours, mut expected) = (s.to_string(), s.to_string());
+        data.uppercase_str_in_place(&mut ours);
+        uppercase_str_in_place(&mut expected);
+        assert_eq!(ours, expected);
+        data.lowercase_str_in_place(&mut ours);
+        lowercase_str_in_place(&mut expected);
+        assert_eq!(ours, expected);
+        assert_eq!(data.lowercase_with_offsets(s), lowercase_with_offsets(s));
+        assert_eq!(data.uppercase_with_offsets(s), uppercase_with_offsets(s));
+        assert_eq!(data.fold_key(s), fold_key(s));
+
+        for &other in &samples {
+            assert_eq!(data.caseless_eq(s, other), caseless_eq(s, other));
+            assert_eq!(data.caseless_find(s, other), caseless_find(s, other));
+            assert_eq!(
+                data.caseless_contains(s, other),
+                caseless_contains(s, other)
+            );
+            assert_eq!(
+                data.caseless_starts_with(s, other),
+                caseless_starts_with(s, other)
+            );
+            assert_eq!(
+                data.caseless_ends_with(s, other),
+                caseless_ends_with(s, other)
+            );
+        }
+    }
+
+    let ranges = ['a'..='z', 'µ'..='ß', 'Ꙁ'..='ꙭ'];
+    assert_eq!(data.case_fold_ranges(&ranges), case_fold_ranges(&ranges));
+
+    for chr in (0..=char::MAX as u32).filter_map(char::from_u32) {
+        assert_eq!(data.chars_folding_to(chr), chars_folding_to(chr));
+        assert_eq!(&*data.case_closure(chr), &*case_closure(chr));
+    }
+}
+
+#[test]
+fn string_conversions_use_loaded_tables() {
+    let bytes = CaseData::builtin().to_bytes();
+    let count = |index: usize| {
+        let pos = 20 + index * 4;
+        let mut count = [0; 4];
+        count.copy_from_slice(&bytes[pos..pos + 4]);
+        u32::from_le_bytes(count) as usize
+    };
+    // Fold Ä to å rather than ä
+    let case_folding = HEADER_LEN + count(0) * 32 + count(1) * 16;
+    let entry = (0..count(2))
+        .map(|i| case_folding + i * 8)
+        .find(|&pos| bytes[pos..pos + 4] == 0xC4u32.to_le_bytes())
+        .unwrap();
+    let mut modified = bytes.clone();
+    modified[entry + 4..entry + 8].copy_from_slice(&('å' as u32).to_le_bytes());
+    let data = CaseData::from_bytes(&with_checksum(modified)).unwrap();
+
+    assert_eq!(data.full_case_fold_str("ÄÖ"), "åö");
+    let mut out = Vec::new();
+    data.case_fold_bytes("ÄÖ".as_bytes(), InvalidUtf8::Replace, &mut out);
+    assert_eq!(out, "åö".as_bytes());
+    assert!(data.caseless_eq("Ä", "å"));
+    assert!(!data.caseless_eq("Ä", "ä"));
+    assert_eq!(data.caseless_find("BRÄNDE", "å"), Some(2..4));
+    // U+212B is ANGSTROM SIGN
+    assert_eq!(data.chars_folding_to('å'), &['Ä', 'Å', 'å', '\u{212B}']);
+    assert_eq!(&*data.case_closure('ä'), &['ä']);
+    assert_eq!(
+        data.case_fold_ranges(&['å'..='å']),
+        vec!['Ä'..='Å', 'å'..='å', '\u{212B}'..='\u{212B}']
+    );
+    assert!(data.caseless_starts_with("ÄRGER", "år"));
+    assert_eq!(data.fold_key("Ä"), data.fold_key("å"));
+    // The lowercase mapping is unchanged
+    assert_eq!(data.lowercase_str("Ä"), "ä");
+}
@@ -0,0 +1,86 @@
+//! Tests of the `unicase-map` command line tool, which is built with the `cli` feature.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn unicase_map(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_unicase-map"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("unable to run unicase-map");
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn converts_stdin() {
+    let input = "Die Straße in İSTANBUL\nǆemal o'neill\n".as_bytes();
+    let cases: &[(&str, &str)] = &[
+        ("lower", "die straße in i\u{307}stanbul\nǆemal o'neill\n"),
+        ("upper", "DIE STRASSE IN İSTANBUL\nǄEMAL O'NEILL\n"),
+        ("title", "Die Straße In İstanbul\nǅemal O'neill\n"),
+        ("fold", "die straße in İstanbul\nǆemal o'neill\n"),
+    ];
+    for &(mode, expected) in cases {
+        let output = unicase_map(&[mode], input);
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            expected,
+            "{}",
+            mode
+        );
+    }
+}
+
+#[test]
+fn passes_invalid_utf8_through() {
+    let output = unicase_map(&["upper", "-"], b"abc\xFF\xE2\x82def");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"ABC\xFF\xE2\x82DEF");
+}
+
+#[test]
+fn inspect() {
+    let output = unicase_map(&["inspect"], "ß".as_bytes());
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "U+00DF 'ß' LATIN SMALL LETTER SHARP S
+    to_lowercase  (maps to itself)
+    to_uppercase  U+0053 'S' LATIN CAPITAL LETTER S, U+0053 'S' LATIN CAPITAL LETTER S
+    to_titlecase  U+0053 'S' LATIN CAPITAL LETTER S, U+0073 's' LATIN SMALL LETTER S
+    case_folded   (maps to itself)
+"
+    );
+}
+
+#[test]
+fn usage_errors() {
+    let output = unicase_map(&["sideways"], b"");
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = unicase_map(&["lower", "does-not-exist.txt"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("unicase-map: does-not-exist.txt: "));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn write_errors() {
+    let input = concat!(env!("CARGO_MANIFEST_DIR"), "/README.md");
+    let output = Command::new(env!("CARGO_BIN_EXE_unicase-map"))
+        .args(&["lower", input])
+        .stdout(std::fs::File::create("/dev/full").unwrap())
+        .output()
+        .expect("unable to run unicase-map");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("unicase-map: <stdout>: "));
+}
@@ -0,0 +1,200 @@
+//! Checks the mapping tables against the Unicode Character Database files they were generated
+//! from. The files are kept in `tests/ucd` and updated by `make test-data`.
+
+use std::char;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+use unicode_case_mapping::{
+    case_folded_u32, full_case_fold_str, simple_lowercase, simple_titlecase, simple_uppercase,
+    to_lowercase_u32, to_titlecase_u32, to_uppercase_u32,
+};
+
+#[derive(Clone, Default)]
+struct Mappings {
+    lower: HashMap<u32, Vec<u32>>,
+    upper: HashMap<u32, Vec<u32>>,
+    title: HashMap<u32, Vec<u32>>,
+}
+
+fn ucd_file(name: &str) -> String {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "ucd", name]
+        .iter()
+        .collect();
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("unable to read {:?}: {}", path, err))
+}
+
+/// Iterate over the fields of the non-comment lines of a UCD file.
+fn records(data: &str) -> impl Iterator<Item = Vec<&str>> {
+    data.lines().filter_map(|line| {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            None
+        } else {
+            Some(line.split(';').map(str::trim).collect())
+        }
+    })
+}
+
+fn parse_codepoint(field: &str) -> u32 {
+    u32::from_str_radix(field, 16).unwrap()
+}
+
+fn parse_codepoints(field: &str) -> Vec<u32> {
+    field.split_whitespace().map(parse_codepoint).collect()
+}
+
+/// Parse the simple mappings in UnicodeData.txt.
+fn simple_mappings(unicode_data: &str) -> Mappings {
+    let mut mappings = Mappings::default();
+    for fields in records(unicode_data) {
+        let cp = parse_codepoint(fields[0]);
+        let (upper, lower, title) = (fields[12], fields[13], fields[14]);
+        if !upper.is_empty() {
+            mappings.upper.insert(cp, vec![parse_codepoint(upper)]);
+        }
+        if !lower.is_empty() {
+            mappings.lower.insert(cp, vec![parse_codepoint(lower)]);
+        }
+        // A missing titlecase mapping is the same as the uppercase mapping
+        let title = if title.is_empty() { upper } else { title };
+        if !title.is_empty() {
+            mappings.title.insert(cp, vec![parse_codepoint(title)]);
+        }
+    }
+    mappings
+}
+
+/// Parse the full mappings: the simple mappings, overridden by the unconditional mappings in
+/// SpecialCasing.txt.
+fn full_mappings(simple: &Mappings, special_casing: &str) -> Mappings {
+    let mut mappings = simple.clone();
+    for fields in records(special_casing) {
+        // Skip conditional mappings, which have a non-empty fifth field
+        if fields.len() > 4 && !fields[4].is_empty() {
+            continue;
+        }
+        let cp = parse_codepoint(fields[0]);
+        mappings.lower.insert(cp, parse_codepoints(fields[1]));
+        mappings.title.insert(cp, parse_codepoints(fields[2]));
+        mappings.upper.insert(cp, parse_codepoints(fields[3]));
+    }
+    mappings
+}
+
+/// Convert a result from this crate to a list of code points, where all zeros means the code
+/// point maps to itself.
+fn mapped(cp: u32, result: &[u32]) -> Vec<u32> {
+    if result.iter().all(|&c| c == 0) {
+        vec![cp]
+    } else {
+        result.iter().cloned().take_while(|&c| c != 0).collect()
+    }
+}
+
+fn expected(cp: u32, table: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+    table.get(&cp).cloned().unwrap_or_else(|| vec![cp])
+}
+
+#[test]
+fn matches_ucd() {
+    let unicode_data = ucd_file("UnicodeData.txt");
+    let special_casing = ucd_file("SpecialCasing.txt");
+    let case_folding = ucd_file("CaseFolding.txt");
+
+    let simple = simple_mappings(&unicode_data);
+    let mappings = full_mappings(&simple, &special_casing);
+    let mut simple_folding = HashMap::new();
+    let mut full_folding = HashMap::new();
+    for fields in records(&case_folding) {
+        let cp = parse_codepoint(fields[0]);
+        let mapping = parse_codepoints(fields[2]);
+        match fields[1] {
+            "C" => {
+                simple_folding.insert(cp, mapping[0]);
+                full_folding.insert(cp, mapping);
+            }
+            "S" => {
+                simple_folding.insert(cp, mapping[0]);
+            }
+            "F" => {
+                full_folding.insert(cp, mapping);
+            }
+            // Turkic mappings are not supported
+            _ => {}
+        }
+    }
+
+    let mut mapped_codepoints: HashSet<u32> = HashSet::new();
+    mapped_codepoints.extend(mappings.lower.keys());
+    mapped_codepoints.extend(mappings.upper.keys());
+    mapped_codepoints.extend(mappings.title.keys());
+    mapped_codepoints.extend(simple_folding.keys());
+
+    for cp in 0..=0x10FFFF {
+        assert_eq!(
+            mapped(cp, &to_lowercase_u32(cp)),
+            expected(cp, &mappings.lower),
+            "lowercase of U+{:04X}",
+            cp
+        );
+        assert_eq!(
+            mapped(cp, &to_uppercase_u32(cp)),
+            expected(cp, &mappings.upper),
+            "uppercase of U+{:04X}",
+            cp
+        );
+        assert_eq!(
+            mapped(cp, &to_titlecase_u32(cp)),
+            expected(cp, &mappings.title),
+            "titlecase of U+{:04X}",
+            cp
+        );
+        assert_eq!(
+            case_folded_u32(cp).map(|folded| folded.get()),
+            simple_folding.get(&cp).cloned(),
+            "case folding of U+{:04X}",
+            cp
+        );
+
+        if !mapped_codepoints.contains(&cp) {
+            assert_eq!(to_lowercase_u32(cp), [0; 2], "U+{:04X} is unmapped", cp);
+            assert_eq!(to_uppercase_u32(cp), [0; 3], "U+{:04X} is unmapped", cp);
+            assert_eq!(to_titlecase_u32(cp), [0; 3], "U+{:04X} is unmapped", cp);
+        }
+
+        let chr = match char::from_u32(cp) {
+            Some(chr) => chr,
+            None => continue,
+        };
+        assert_eq!(
+            vec![simple_lowercase(chr) as u32],
+            expected(cp, &simple.lower),
+            "simple lowercase of U+{:04X}",
+            cp
+        );
+        assert_eq!(
+            vec![simple_uppercase(chr) as u32],
+            expected(cp, &simple.upper),
+            "simple uppercase of U+{:04X}",
+            cp
+        );
+        assert_eq!(
+            vec![simple_titlecase(chr) as u32],
+            expected(cp, &simple.title),
+            "simple titlecase of U+{:04X}",
+            cp
+        );
+        let folded: Vec<u32> = full_case_fold_str(&chr.to_string())
+            .chars()
+            .map(|c| c as u32)
+            .collect();
+        assert_eq!(
+            folded,
+            expected(cp, &full_folding),
+            "full case folding of U+{:04X}",
+            cp
+        );
+    }
+}
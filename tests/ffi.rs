@@ -0,0 +1,72 @@
+//! Tests of the C ABI, which is built with the `ffi` feature. See also `tests/c/ffi_test.c`.
+
+use std::ptr;
+
+use unicode_case_mapping::ffi::*;
+
+#[test]
+fn code_points() {
+    let mut out = [0xFFFF; 3];
+    unsafe {
+        assert_eq!(ucm_to_lowercase(0x0130, out.as_mut_ptr()), 2);
+        assert_eq!(out, [0x69, 0x0307, 0]);
+        assert_eq!(ucm_to_uppercase(0xDF, out.as_mut_ptr()), 2);
+        assert_eq!(out, [0x53, 0x53, 0]);
+        assert_eq!(ucm_to_titlecase(0x01C6, out.as_mut_ptr()), 1);
+        assert_eq!(out, [0x01C5, 0, 0]);
+        assert_eq!(ucm_to_lowercase(0x110000, out.as_mut_ptr()), 0);
+        assert_eq!(out, [0; 3]);
+    }
+    assert_eq!(ucm_case_folded(0x1E9E), 0xDF);
+    assert_eq!(ucm_case_folded(0xDF), 0);
+}
+
+#[test]
+fn utf8_buffers() {
+    let input = b"Stra\xC3\x9Fe\xFF";
+    let mut out = [0; 16];
+    unsafe {
+        let len = ucm_uppercase_utf8(
+            input.as_ptr(),
+            input.len(),
+            ptr::null_mut(),
+            0,
+            UCM_INVALID_UTF8_REPLACE,
+        );
+        assert_eq!(len, 10);
+
+        // A buffer that is too small is left untouched
+        let short = ucm_uppercase_utf8(
+            input.as_ptr(),
+            input.len(),
+            out.as_mut_ptr(),
+            len - 1,
+            UCM_INVALID_UTF8_REPLACE,
+        );
+        assert_eq!(short, len);
+        assert_eq!(out, [0; 16]);
+
+        let written = ucm_uppercase_utf8(
+            input.as_ptr(),
+            input.len(),
+            out.as_mut_ptr(),
+            out.len(),
+            UCM_INVALID_UTF8_REPLACE,
+        );
+        assert_eq!(&out[..written], "STRASSE\u{FFFD}".as_bytes());
+
+        let written = ucm_case_fold_utf8(
+            input.as_ptr(),
+            input.len(),
+            out.as_mut_ptr(),
+            out.len(),
+            UCM_INVALID_UTF8_PASS_THROUGH,
+        );
+        assert_eq!(&out[..written], b"stra\xC3\x9Fe\xFF");
+
+        assert_eq!(
+            ucm_lowercase_utf8(ptr::null(), 0, ptr::null_mut(), 0, UCM_INVALID_UTF8_REPLACE),
+            0
+        );
+    }
+}
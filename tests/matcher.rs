@@ -0,0 +1,78 @@
+//! Tests of matching many patterns at once with `CaselessMatcher`.
+
+use std::ops::Range;
+
+use unicode_case_mapping::CaselessMatcher;
+
+/// Returns the pattern index and range of every match of `patterns` in `haystack`.
+fn matches(patterns: &[&str], haystack: &str) -> Vec<(usize, Range<usize>)> {
+    CaselessMatcher::new(patterns)
+        .find_iter(haystack)
+        .map(|m| (m.pattern(), m.range()))
+        .collect()
+}
+
+#[test]
+fn overlapping_patterns() {
+    assert_eq!(matches(&["abc", "bcd"], "ABCD"), vec![(0, 0..3), (1, 1..4)]);
+    assert_eq!(
+        matches(&["aa"], "AAAA"),
+        vec![(0, 0..2), (0, 1..3), (0, 2..4)]
+    );
+}
+
+#[test]
+fn pattern_that_is_a_prefix_of_another() {
+    assert_eq!(
+        matches(&["hello", "HE"], "Hello"),
+        vec![(1, 0..2), (0, 0..5)]
+    );
+    // Matches that end at the same offset are returned longest first
+    assert_eq!(
+        matches(&["lo", "hello"], "Hello"),
+        vec![(1, 0..5), (0, 3..5)]
+    );
+}
+
+#[test]
+fn duplicate_patterns() {
+    let found = matches(&["straße", "STRASSE", "straße"], "Die Strasse");
+    assert_eq!(found.len(), 3);
+    let mut patterns: Vec<usize> = found.iter().map(|(pattern, _)| *pattern).collect();
+    patterns.sort();
+    assert_eq!(patterns, vec![0, 1, 2]);
+    assert!(found.iter().all(|(_, range)| *range == (4..11)));
+}
+
+#[test]
+fn matches_inside_an_expansion_are_rejected() {
+    // `ß` folds to `ss`, so a match must cover both characters of its expansion
+    assert_eq!(matches(&["s"], "ß"), vec![]);
+    assert_eq!(matches(&["s"], "sß"), vec![(0, 0..1)]);
+    assert_eq!(matches(&["ss"], "ß"), vec![(0, 0..2)]);
+    assert_eq!(matches(&["ss"], "ẞ"), vec![(0, 0..3)]);
+    // U+FB03 LATIN SMALL LIGATURE FFI folds to `ffi`
+    assert_eq!(matches(&["f", "fi", "ff"], "\u{FB03}"), vec![]);
+    assert_eq!(matches(&["ffi"], "\u{FB03}"), vec![(0, 0..3)]);
+}
+
+#[test]
+fn matches_crossing_an_expansion_boundary() {
+    assert_eq!(matches(&["asse"], "aße"), vec![(0, 0..4)]);
+    assert_eq!(matches(&["strasse"], "STRAẞE"), vec![(0, 0..8)]);
+    // Starting or ending part way through the expansion of `ß` doesn't match
+    assert_eq!(matches(&["se"], "aße"), vec![]);
+    assert_eq!(matches(&["as"], "aße"), vec![]);
+    assert_eq!(matches(&["se", "asse"], "aße"), vec![(1, 0..4)]);
+}
+
+#[test]
+fn empty_patterns_and_input() {
+    let no_patterns: [&str; 0] = [];
+    assert_eq!(matches(&no_patterns, "text"), vec![]);
+    assert!(!CaselessMatcher::new(&no_patterns).is_match(""));
+    assert_eq!(matches(&["a", "b"], ""), vec![]);
+    assert_eq!(matches(&[""], "text"), vec![]);
+    assert_eq!(matches(&[""], ""), vec![]);
+    assert_eq!(matches(&["", "x"], "TEXT"), vec![(1, 2..3)]);
+}
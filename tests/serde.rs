@@ -0,0 +1,172 @@
+//! Tests of the serde implementations, which are enabled with the `serde` feature.
+//!
+//! These use the deserializers built in to serde, so that no serialization format is needed.
+
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer, StrDeserializer};
+use serde::de::{self, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use serde::Deserialize;
+
+use unicode_case_mapping::{
+    case_closure, lowercase_with_offsets, CaseClosure, Caseless, CaselessMatch, CaselessMatcher,
+    FoldedString, InvalidUtf8, OffsetMap, OffsetSegment,
+};
+
+/// A deserializable value, for types that deserialize from structs.
+#[derive(Clone)]
+enum Value {
+    Usize(usize),
+    Bool(bool),
+    Seq(Vec<Value>),
+    Struct(Vec<(&'static str, Value)>),
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Usize(value) => visitor.visit_u64(value as u64),
+            Value::Bool(value) => visitor.visit_bool(value),
+            Value::Seq(values) => visitor.visit_seq(SeqDeserializer::new(values.into_iter())),
+            Value::Struct(fields) => visitor.visit_map(MapDeserializer::new(fields.into_iter())),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+fn range(start: usize, end: usize) -> Value {
+    Value::Struct(vec![
+        ("start", Value::Usize(start)),
+        ("end", Value::Usize(end)),
+    ])
+}
+
+fn segment(segment: &OffsetSegment) -> Value {
+    Value::Struct(vec![
+        ("source", range(segment.source.start, segment.source.end)),
+        ("output", range(segment.output.start, segment.output.end)),
+        ("one_to_one", Value::Bool(segment.is_one_to_one())),
+    ])
+}
+
+fn caseless_match(pattern: usize, start: usize, end: usize) -> Value {
+    Value::Struct(vec![
+        ("pattern", Value::Usize(pattern)),
+        ("start", Value::Usize(start)),
+        ("end", Value::Usize(end)),
+    ])
+}
+
+fn from_str<'de, T: Deserialize<'de>>(s: &'de str) -> Result<T, Error> {
+    let deserializer: StrDeserializer<'de, Error> = s.into_deserializer();
+    T::deserialize(deserializer)
+}
+
+fn from_chars<'de, T: Deserialize<'de>>(chars: &[char]) -> Result<T, Error> {
+    let deserializer: SeqDeserializer<_, Error> = SeqDeserializer::new(chars.iter().cloned());
+    T::deserialize(deserializer)
+}
+
+#[test]
+fn folded_string() {
+    let folded: FoldedString = from_str("strasse σίσυφοσ").unwrap();
+    assert_eq!(folded, FoldedString::new("Straße ΣΊΣΥΦΟΣ"));
+
+    // Each of these changes when case folded
+    for unfolded in &["Strasse", "straße", "ﬃ", "σίσυφοΣ", "k\u{212A}"] {
+        let err = from_str::<FoldedString>(unfolded).unwrap_err();
+        assert!(err.to_string().starts_with("string is not case folded"));
+    }
+}
+
+#[test]
+fn caseless() {
+    let caseless: Caseless<String> = from_str("Straße").unwrap();
+    assert_eq!(caseless, Caseless("STRASSE"));
+}
+
+#[test]
+fn case_closures() {
+    let closure: CaseClosure = from_chars(&['K', 'k', '\u{212A}']).unwrap();
+    assert_eq!(*closure, *case_closure('k'));
+    let closure: CaseClosure = from_chars(&['-']).unwrap();
+    assert_eq!(*closure, *case_closure('-'));
+
+    assert!(from_chars::<CaseClosure>(&['K', 'k']).is_err());
+    assert!(from_chars::<CaseClosure>(&['k', 'K', '\u{212A}']).is_err());
+    assert!(from_chars::<CaseClosure>(&[]).is_err());
+}
+
+#[test]
+fn invalid_utf8() {
+    assert_eq!(from_str::<InvalidUtf8>("Replace"), Ok(InvalidUtf8::Replace));
+    assert!(from_str::<InvalidUtf8>("Ignore").is_err());
+}
+
+#[test]
+fn offset_maps() {
+    let (_, offsets) = lowercase_with_offsets("İstanbul İzmir");
+    assert_eq!(offsets.segments().len(), 4);
+    let segments: Vec<Value> = offsets.segments().iter().map(segment).collect();
+    let deserialized = OffsetMap::deserialize(Value::Seq(segments.clone())).unwrap();
+    assert_eq!(deserialized, offsets);
+
+    // Segments that skip part of the string or are out of order
+    let mut gap = segments.clone();
+    gap.remove(1);
+    let mut out_of_order = segments.clone();
+    out_of_order.swap(0, 1);
+    for invalid in vec![gap, out_of_order] {
+        let err = OffsetMap::deserialize(Value::Seq(invalid)).unwrap_err();
+        assert_eq!(err.to_string(), "offset map segments are not contiguous");
+    }
+
+    // A segment that runs backwards, or a one-to-one segment that has a different length in the
+    // source and the output
+    let backwards = Value::Struct(vec![
+        ("source", range(2, 0)),
+        ("output", range(0, 2)),
+        ("one_to_one", Value::Bool(false)),
+    ]);
+    let mismatched = Value::Struct(vec![
+        ("source", range(0, 2)),
+        ("output", range(0, 3)),
+        ("one_to_one", Value::Bool(true)),
+    ]);
+    for invalid in vec![backwards, mismatched] {
+        let message = "offset segment ends before it starts or has mismatched lengths";
+        let err = OffsetSegment::deserialize(invalid.clone()).unwrap_err();
+        assert_eq!(err.to_string(), message);
+        let err = OffsetMap::deserialize(Value::Seq(vec![invalid])).unwrap_err();
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[test]
+fn caseless_matches() {
+    let matcher = CaselessMatcher::new(&["strasse"]);
+    let found = matcher.find_iter("Die Straße").next().unwrap();
+    let deserialized = CaselessMatch::deserialize(caseless_match(0, 4, 11)).unwrap();
+    assert_eq!(deserialized, found);
+    assert_eq!(
+        CaselessMatch::deserialize(caseless_match(0, 4, 4)).map(|m| m.range()),
+        Ok(4..4)
+    );
+
+    let err = CaselessMatch::deserialize(caseless_match(0, 11, 4)).unwrap_err();
+    assert_eq!(err.to_string(), "caseless match ends before it starts");
+}
@@ -0,0 +1,253 @@
+//! Compares the lowercase and uppercase mappings of every code point with the standard library.
+//!
+//! The standard library and this crate may be built from different versions of the Unicode
+//! Character Database. Differences are grouped by cause: characters that only one side has case
+//! data for are attributed to version skew, any other difference is a conflict. No skew is
+//! expected when both sides use the same version, and the expected skew is recorded below for the
+//! other UCD versions used by the standard library, so upgrading the UCD of this crate fails the
+//! test with a diff of the skew until the record is updated. With a version of std that has no
+//! record, the differences are printed instead of checked.
+
+use std::char;
+
+use unicode_case_mapping::{to_lowercase, to_uppercase, UNICODE_VERSION};
+
+struct ExpectedSkew {
+    /// The Unicode version of the standard library's case data
+    unicode_version: (u8, u8, u8),
+    std_skew: &'static [&'static str],
+    crate_skew: &'static [&'static str],
+}
+
+const EXPECTED_SKEW: &[ExpectedSkew] = &[ExpectedSkew {
+    unicode_version: (17, 0, 0),
+    std_skew: &[],
+    crate_skew: &[
+        "Lower of U+A7CE: crate U+A7CE, std U+A7CF",
+        "Upper of U+A7CF: crate U+A7CF, std U+A7CE",
+        "Lower of U+A7D2: crate U+A7D2, std U+A7D3",
+        "Upper of U+A7D3: crate U+A7D3, std U+A7D2",
+        "Lower of U+A7D4: crate U+A7D4, std U+A7D5",
+        "Upper of U+A7D5: crate U+A7D5, std U+A7D4",
+        "Lower of U+16EA0: crate U+16EA0, std U+16EBB",
+        "Lower of U+16EA1: crate U+16EA1, std U+16EBC",
+        "Lower of U+16EA2: crate U+16EA2, std U+16EBD",
+        "Lower of U+16EA3: crate U+16EA3, std U+16EBE",
+        "Lower of U+16EA4: crate U+16EA4, std U+16EBF",
+        "Lower of U+16EA5: crate U+16EA5, std U+16EC0",
+        "Lower of U+16EA6: crate U+16EA6, std U+16EC1",
+        "Lower of U+16EA7: crate U+16EA7, std U+16EC2",
+        "Lower of U+16EA8: crate U+16EA8, std U+16EC3",
+        "Lower of U+16EA9: crate U+16EA9, std U+16EC4",
+        "Lower of U+16EAA: crate U+16EAA, std U+16EC5",
+        "Lower of U+16EAB: crate U+16EAB, std U+16EC6",
+        "Lower of U+16EAC: crate U+16EAC, std U+16EC7",
+        "Lower of U+16EAD: crate U+16EAD, std U+16EC8",
+        "Lower of U+16EAE: crate U+16EAE, std U+16EC9",
+        "Lower of U+16EAF: crate U+16EAF, std U+16ECA",
+        "Lower of U+16EB0: crate U+16EB0, std U+16ECB",
+        "Lower of U+16EB1: crate U+16EB1, std U+16ECC",
+        "Lower of U+16EB2: crate U+16EB2, std U+16ECD",
+        "Lower of U+16EB3: crate U+16EB3, std U+16ECE",
+        "Lower of U+16EB4: crate U+16EB4, std U+16ECF",
+        "Lower of U+16EB5: crate U+16EB5, std U+16ED0",
+        "Lower of U+16EB6: crate U+16EB6, std U+16ED1",
+        "Lower of U+16EB7: crate U+16EB7, std U+16ED2",
+        "Lower of U+16EB8: crate U+16EB8, std U+16ED3",
+        "Upper of U+16EBB: crate U+16EBB, std U+16EA0",
+        "Upper of U+16EBC: crate U+16EBC, std U+16EA1",
+        "Upper of U+16EBD: crate U+16EBD, std U+16EA2",
+        "Upper of U+16EBE: crate U+16EBE, std U+16EA3",
+        "Upper of U+16EBF: crate U+16EBF, std U+16EA4",
+        "Upper of U+16EC0: crate U+16EC0, std U+16EA5",
+        "Upper of U+16EC1: crate U+16EC1, std U+16EA6",
+        "Upper of U+16EC2: crate U+16EC2, std U+16EA7",
+        "Upper of U+16EC3: crate U+16EC3, std U+16EA8",
+        "Upper of U+16EC4: crate U+16EC4, std U+16EA9",
+        "Upper of U+16EC5: crate U+16EC5, std U+16EAA",
+        "Upper of U+16EC6: crate U+16EC6, std U+16EAB",
+        "Upper of U+16EC7: crate U+16EC7, std U+16EAC",
+        "Upper of U+16EC8: crate U+16EC8, std U+16EAD",
+        "Upper of U+16EC9: crate U+16EC9, std U+16EAE",
+        "Upper of U+16ECA: crate U+16ECA, std U+16EAF",
+        "Upper of U+16ECB: crate U+16ECB, std U+16EB0",
+        "Upper of U+16ECC: crate U+16ECC, std U+16EB1",
+        "Upper of U+16ECD: crate U+16ECD, std U+16EB2",
+        "Upper of U+16ECE: crate U+16ECE, std U+16EB3",
+        "Upper of U+16ECF: crate U+16ECF, std U+16EB4",
+        "Upper of U+16ED0: crate U+16ED0, std U+16EB5",
+        "Upper of U+16ED1: crate U+16ED1, std U+16EB6",
+        "Upper of U+16ED2: crate U+16ED2, std U+16EB7",
+        "Upper of U+16ED3: crate U+16ED3, std U+16EB8",
+    ],
+}];
+
+#[derive(Debug, Copy, Clone)]
+enum Mapping {
+    Lower,
+    Upper,
+}
+
+struct Difference {
+    chr: char,
+    mapping: Mapping,
+    ours: Vec<char>,
+    std: Vec<char>,
+}
+
+#[derive(Default)]
+struct Report {
+    /// The standard library has no case data for these characters
+    std_skew: Vec<Difference>,
+    /// This crate has no case data for these characters
+    crate_skew: Vec<Difference>,
+    /// Both sides have case data but disagree
+    conflicts: Vec<Difference>,
+}
+
+fn ours(chr: char, mapping: Mapping) -> Vec<char> {
+    let mut mapped = [0; 3];
+    match mapping {
+        Mapping::Lower => mapped[..2].copy_from_slice(&to_lowercase(chr)),
+        Mapping::Upper => mapped = to_uppercase(chr),
+    }
+    if mapped[0] == 0 {
+        return vec![chr];
+    }
+    mapped
+        .iter()
+        .take_while(|&&cp| cp != 0)
+        .map(|&cp| char::from_u32(cp).unwrap())
+        .collect()
+}
+
+fn std(chr: char, mapping: Mapping) -> Vec<char> {
+    match mapping {
+        Mapping::Lower => chr.to_lowercase().collect(),
+        Mapping::Upper => chr.to_uppercase().collect(),
+    }
+}
+
+/// Returns true if the standard library has case data for `chr`.
+fn std_is_cased(chr: char) -> bool {
+    chr.is_lowercase()
+        || chr.is_uppercase()
+        || chr.to_lowercase().ne(Some(chr))
+        || chr.to_uppercase().ne(Some(chr))
+}
+
+/// Returns true if this crate has case data for `chr`.
+fn crate_is_cased(chr: char) -> bool {
+    to_lowercase(chr)[0] != 0 || to_uppercase(chr)[0] != 0
+}
+
+fn format_chars(chars: &[char]) -> String {
+    chars
+        .iter()
+        .map(|&c| format!("U+{:04X}", c as u32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn format_differences(differences: &[Difference]) -> Vec<String> {
+    differences
+        .iter()
+        .map(|difference| {
+            format!(
+                "{:?} of U+{:04X}: crate {}, std {}",
+                difference.mapping,
+                difference.chr as u32,
+                format_chars(&difference.ours),
+                format_chars(&difference.std)
+            )
+        })
+        .collect()
+}
+
+#[rustversion::since(1.45)]
+fn std_unicode_version() -> Option<(u8, u8, u8)> {
+    Some(char::UNICODE_VERSION)
+}
+
+/// `char::UNICODE_VERSION` is not available, so the skew can't be checked.
+#[rustversion::before(1.45)]
+fn std_unicode_version() -> Option<(u8, u8, u8)> {
+    None
+}
+
+/// Returns the expected skew for the Unicode version of std, as the differences with no case data
+/// in std and the differences with no case data in this crate.
+fn expected_skew(
+    version: (u8, u8, u8),
+) -> Option<(&'static [&'static str], &'static [&'static str])> {
+    let (major, minor, update) = version;
+    if (u64::from(major), u64::from(minor), u64::from(update)) == UNICODE_VERSION {
+        return Some((&[], &[]));
+    }
+    EXPECTED_SKEW
+        .iter()
+        .find(|expected| expected.unicode_version == version)
+        .map(|expected| (expected.std_skew, expected.crate_skew))
+}
+
+#[test]
+fn matches_std() {
+    let mut report = Report::default();
+    for chr in (0..=0x10FFFF).filter_map(char::from_u32) {
+        for &mapping in &[Mapping::Lower, Mapping::Upper] {
+            let ours = ours(chr, mapping);
+            let std = std(chr, mapping);
+            if ours == std {
+                continue;
+            }
+
+            let std_unknown = ours.iter().chain(Some(&chr)).any(|&c| !std_is_cased(c));
+            let crate_unknown = std.iter().chain(Some(&chr)).any(|&c| !crate_is_cased(c));
+            let difference = Difference {
+                chr,
+                mapping,
+                ours,
+                std,
+            };
+            if std_unknown && difference.std == [chr] {
+                report.std_skew.push(difference);
+            } else if crate_unknown && difference.ours == [chr] {
+                report.crate_skew.push(difference);
+            } else {
+                report.conflicts.push(difference);
+            }
+        }
+    }
+
+    let conflicts = format_differences(&report.conflicts);
+    let std_skew = format_differences(&report.std_skew);
+    let crate_skew = format_differences(&report.crate_skew);
+    let expected = match std_unicode_version() {
+        Some(version) => match expected_skew(version) {
+            Some(expected) => Some(expected),
+            None => {
+                eprintln!(
+                    "no expected skew recorded for Unicode {:?} in std\nconflicting mappings: {:#?}\nno case data in std: {:#?}\nno case data in this crate: {:#?}",
+                    version, conflicts, std_skew, crate_skew
+                );
+                return;
+            }
+        },
+        None => None,
+    };
+
+    assert!(
+        conflicts.is_empty(),
+        "conflicting mappings:\n{:#?}",
+        conflicts
+    );
+    let (std_expected, crate_expected) = match expected {
+        Some(expected) => expected,
+        None => return,
+    };
+    assert_eq!(std_skew, std_expected, "version skew, no case data in std");
+    assert_eq!(
+        crate_skew, crate_expected,
+        "version skew, no case data in this crate"
+    );
+}
@@ -0,0 +1,103 @@
+//! Tests of the string conversions that rewrite their input in place or restrict its length.
+
+use unicode_case_mapping::{
+    lowercase_in_place, lowercase_str, lowercase_str_in_place, lowercase_str_with,
+    uppercase_in_place, uppercase_str, uppercase_str_in_place, uppercase_str_with, LengthPolicy,
+};
+
+fn lowercased(s: &str) -> String {
+    let mut s = s.to_string();
+    lowercase_in_place(&mut s);
+    s
+}
+
+fn uppercased(s: &str) -> String {
+    let mut s = s.to_string();
+    uppercase_in_place(&mut s);
+    s
+}
+
+#[test]
+fn expansion_falls_back_to_converting_the_rest() {
+    // U+0149 LATIN SMALL LETTER N PRECEDED BY APOSTROPHE uppercases to U+02BC N
+    assert_eq!(uppercased("abc \u{149} def"), "ABC \u{2BC}N DEF");
+    assert_eq!(uppercased("die straße und weg"), "DIE STRASSE UND WEG");
+    assert_eq!(uppercased("ßß"), "SSSS");
+    assert_eq!(
+        lowercased("İSTANBUL İZMİR"),
+        "i\u{307}stanbul i\u{307}zmi\u{307}r"
+    );
+}
+
+#[test]
+fn shrinking_mappings() {
+    // U+1E9E LATIN CAPITAL LETTER SHARP S is longer than ß
+    assert_eq!(lowercased("GROẞE"), "große");
+    // ı is longer than I
+    assert_eq!(uppercased("ıııabc"), "IIIABC");
+    // Characters that shrink leave room for an expansion later on
+    assert_eq!(uppercased("ıı\u{149}"), "II\u{2BC}N");
+
+    let mut s = String::from("ẞ ẞ ẞ ẞ ẞ ẞ");
+    let ptr = s.as_ptr();
+    lowercase_in_place(&mut s);
+    assert_eq!(s, "ß ß ß ß ß ß");
+    assert_eq!(s.as_ptr(), ptr);
+}
+
+#[test]
+fn mixed_ascii_and_non_ascii_runs() {
+    let samples = [
+        "The QUICK brown FOX jumps over the LAZY dog",
+        "ascii words longer than eight bytes ΑΒΓΔΕ more ascii words follow",
+        "Straße ıı İstanbul \u{149}ew ﬃ ǅemal ẞ ΣΊΣΥΦΟΣ 𐐀𐐨 end",
+        "ıııııııııııııııı then ascii ŉŉŉŉŉŉŉŉ then more ascii",
+        "日本語のテキスト with ASCII",
+    ];
+    for &s in &samples {
+        assert_eq!(lowercased(s), lowercase_str(s), "{}", s);
+        assert_eq!(uppercased(s), uppercase_str(s), "{}", s);
+    }
+}
+
+#[test]
+fn str_in_place_preserves_utf8_length() {
+    let samples = [
+        "Straße ıı İstanbul \u{149}ew ﬃ ǅemal ẞ ΣΊΣΥΦΟΣ 𐐀𐐨 end",
+        "ascii words longer than eight bytes ΑΒΓΔΕ more ascii words follow",
+        "ȿ Ȿ ɐ Ɐ K Å",
+    ];
+    for &s in &samples {
+        let mut lower = s.to_string();
+        lowercase_str_in_place(&mut lower);
+        assert_eq!(lower, lowercase_str_with(s, LengthPolicy::PreserveUtf8Len));
+        assert_eq!(lower.len(), s.len());
+
+        let mut upper = s.to_string();
+        uppercase_str_in_place(&mut upper);
+        assert_eq!(upper, uppercase_str_with(s, LengthPolicy::PreserveUtf8Len));
+        assert_eq!(upper.len(), s.len());
+    }
+
+    let mut s = String::from("straße ı \u{149}");
+    uppercase_str_in_place(&mut s);
+    assert_eq!(s, "STRASSE ı \u{149}");
+}
+
+#[test]
+fn preserve_utf8_len() {
+    let policy = LengthPolicy::PreserveUtf8Len;
+    // ȿ and Ȿ map to each other but have different UTF-8 lengths, so are left unchanged
+    assert_eq!(uppercase_str("ȿ"), "Ȿ");
+    assert_eq!(uppercase_str_with("ȿ", policy), "ȿ");
+    assert_eq!(lowercase_str("Ȿ"), "ȿ");
+    assert_eq!(lowercase_str_with("Ȿ", policy), "Ȿ");
+    // SS is the same length as ß, while its simple mapping ẞ is longer
+    assert_eq!(uppercase_str_with("ß", policy), "SS");
+    assert_eq!(lowercase_str_with("ẞ", policy), "ẞ");
+
+    for &s in &["ȿ Ȿ ß ẞ", "Straße ȾȿɐⱯ İı \u{149}", "ascii only"] {
+        assert_eq!(lowercase_str_with(s, policy).len(), s.len(), "{}", s);
+        assert_eq!(uppercase_str_with(s, policy).len(), s.len(), "{}", s);
+    }
+}
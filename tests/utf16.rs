@@ -0,0 +1,51 @@
+//! Tests of the UTF-16 conversions with supplementary plane characters.
+
+use unicode_case_mapping::{lowercase_utf16, uppercase_utf16};
+
+fn lower(input: &[u16]) -> Vec<u16> {
+    let mut out = Vec::new();
+    lowercase_utf16(input, &mut out);
+    out
+}
+
+fn upper(input: &[u16]) -> Vec<u16> {
+    let mut out = Vec::new();
+    uppercase_utf16(input, &mut out);
+    out
+}
+
+#[test]
+fn supplementary_plane_round_trip() {
+    let pairs = [
+        // DESERET CAPITAL/SMALL LETTER LONG I
+        ('\u{10400}', '\u{10428}'),
+        // OSAGE CAPITAL/SMALL LETTER A
+        ('\u{104B0}', '\u{104D8}'),
+        // ADLAM CAPITAL/SMALL LETTER ALIF
+        ('\u{1E900}', '\u{1E922}'),
+    ];
+    for &(capital, small) in &pairs {
+        let capital: Vec<u16> = capital.to_string().encode_utf16().collect();
+        let small: Vec<u16> = small.to_string().encode_utf16().collect();
+        assert_eq!(capital.len(), 2);
+        assert_eq!(lower(&capital), small);
+        assert_eq!(upper(&small), capital);
+        assert_eq!(upper(&lower(&capital)), capital);
+        assert_eq!(lower(&upper(&small)), small);
+    }
+}
+
+#[test]
+fn unpaired_surrogates() {
+    // U+104B0 OSAGE CAPITAL LETTER A and U+1E922 ADLAM SMALL LETTER ALIF, each followed by an
+    // unpaired low surrogate, then an unpaired high surrogate at the end
+    let input = [0xD801, 0xDCB0, 0xDC00, 0xD83A, 0xDD22, 0xDFFF, 0xD801];
+    assert_eq!(
+        lower(&input),
+        [0xD801, 0xDCD8, 0xDC00, 0xD83A, 0xDD22, 0xDFFF, 0xD801]
+    );
+    assert_eq!(
+        upper(&input),
+        [0xD801, 0xDCB0, 0xDC00, 0xD83A, 0xDD00, 0xDFFF, 0xD801]
+    );
+}
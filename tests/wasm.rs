@@ -0,0 +1,38 @@
+//! Tests of the WebAssembly bindings, which are built with the `wasm` feature.
+//!
+//! These run under `wasm32-unknown-unknown` in a headless browser, without Node.js:
+//!
+//! ```sh
+//! cargo install wasm-bindgen-cli
+//! CHROMEDRIVER=chromedriver cargo test --target wasm32-unknown-unknown --features wasm --test wasm
+//! ```
+
+#![cfg(target_arch = "wasm32")]
+
+use unicode_case_mapping::wasm::{fold, lowercase, titlecase, unicode_version, uppercase};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn converts_case() {
+    assert_eq!(lowercase("HELLO İSTANBUL"), "hello i\u{307}stanbul");
+    assert_eq!(uppercase("Straße"), "STRASSE");
+    assert_eq!(titlecase("ǆEMAL o'NEILL ßeta"), "ǅemal O'neill Sseta");
+    assert_eq!(fold("ΣΊΣΥΦΟΣ ẞ ß"), "σίσυφοσ ss ss");
+}
+
+#[wasm_bindgen_test]
+fn matches_rust_functions() {
+    let text = "Ελληνικά Русский Türkçe ქართული 日本語 😀";
+    assert_eq!(lowercase(text), unicode_case_mapping::lowercase_str(text));
+    assert_eq!(uppercase(text), unicode_case_mapping::uppercase_str(text));
+    assert_eq!(titlecase(text), unicode_case_mapping::titlecase_str(text));
+    assert_eq!(fold(text), unicode_case_mapping::full_case_fold_str(text));
+}
+
+#[wasm_bindgen_test]
+fn reports_unicode_version() {
+    let (major, minor, update) = unicode_case_mapping::UNICODE_VERSION;
+    assert_eq!(unicode_version(), format!("{}.{}.{}", major, minor, update));
+}